@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+alter table users
+drop column quiet_hours_start,
+drop column quiet_hours_end;
+
+drop table email_unsubscribes;
+
+drop table notification_preferences;
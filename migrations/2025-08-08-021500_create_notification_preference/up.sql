@@ -0,0 +1,24 @@
+-- Your SQL goes here
+
+create table notification_preferences (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid not null references users(id),
+  category varchar(20) not null,
+  in_app boolean not null default true,
+  push boolean not null default true,
+  email boolean not null default true,
+  created_at timestamp not null default now(),
+  unique (user_id, category)
+);
+
+create table email_unsubscribes (
+  id uuid primary key default gen_random_uuid(),
+  email varchar not null,
+  category varchar(20) not null,
+  created_at timestamp not null default now(),
+  unique (email, category)
+);
+
+alter table users
+add column quiet_hours_start time,
+add column quiet_hours_end time;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+create table levels (
+  level integer primary key,
+  exp_required integer not null,
+  reward_type varchar(20),
+  reward_count integer,
+  created_at timestamp not null default now()
+);
+
+insert into levels (level, exp_required, reward_type, reward_count)
+select l, 200 + (l - 1) * 50, 'ROUTE_CALCULATION', case when l % 5 = 0 then 3 else 1 end
+from generate_series(1, 50) as l;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+alter table missions
+drop column period,
+drop column active_from,
+drop column active_until;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+
+alter table missions
+add column period varchar(10) not null default 'daily',
+add column active_from timestamp,
+add column active_until timestamp;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+
+create table check_in_streaks (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid unique not null references users(id),
+  current_streak integer not null default 0,
+  longest_streak integer not null default 0,
+  last_check_in_date date,
+  freeze_tokens integer not null default 0,
+  created_at timestamp not null default now()
+);
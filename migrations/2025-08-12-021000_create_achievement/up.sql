@@ -0,0 +1,29 @@
+-- Your SQL goes here
+
+create table achievements (
+  id uuid primary key default gen_random_uuid(),
+  code varchar(30) unique not null,
+  name varchar(50) not null,
+  description text,
+  icon text,
+  metric varchar(20) not null,
+  place_type varchar,
+  threshold integer not null,
+  created_at timestamp not null default now()
+);
+
+create table user_achievements (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid not null references users(id),
+  achievement_id uuid not null references achievements(id),
+  unlocked_at timestamp not null default now(),
+  unique (user_id, achievement_id)
+);
+
+insert into achievements (code, name, description, metric, place_type, threshold) values
+  ('FIRST_REVIEW', 'First Review', 'Write your first review.', 'REVIEW_COUNT', null, 1),
+  ('REVIEWER_10', 'Critic', 'Write 10 reviews.', 'REVIEW_COUNT', null, 10),
+  ('EXPLORER_10', 'Explorer', 'Visit 10 different places.', 'PLACE_VISITS', null, 10),
+  ('CAFE_HOPPER_10', 'Café Hopper', 'Visit 10 different cafés.', 'PLACE_VISITS', 'cafe', 10),
+  ('LEVEL_10', 'Rising Star', 'Reach level 10.', 'LEVEL', null, 10),
+  ('INVITE_5', 'Ambassador', 'Invite 5 friends.', 'INVITE_COUNT', null, 5);
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+
+create table follows (
+  id uuid primary key default gen_random_uuid(),
+  follower_id uuid not null references users(id),
+  followee_id uuid not null references users(id),
+  created_at timestamp not null default now(),
+  unique (follower_id, followee_id)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+alter table missions
+drop column gift_reward_code;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+
+alter table missions
+add column gift_reward_code varchar(30) references achievements(code) on delete set null;
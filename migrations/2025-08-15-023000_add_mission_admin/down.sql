@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+alter table missions
+drop column is_disabled;
+
+alter table users
+drop column is_admin;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+alter table users
+add column is_admin boolean not null default false;
+
+alter table missions
+add column is_disabled boolean not null default false;
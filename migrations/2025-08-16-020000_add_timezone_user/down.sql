@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+alter table users
+drop column timezone;
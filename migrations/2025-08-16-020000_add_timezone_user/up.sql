@@ -0,0 +1,4 @@
+-- Your SQL goes here
+
+alter table users
+add column timezone varchar(64) not null default 'UTC';
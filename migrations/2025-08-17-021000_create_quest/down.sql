@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+drop table user_quests;
+
+drop table quest_steps;
+
+drop table quests;
@@ -0,0 +1,34 @@
+-- Your SQL goes here
+
+create table quests (
+  id uuid primary key default gen_random_uuid(),
+  code varchar(30) unique not null,
+  name varchar(50) not null,
+  description text,
+  period varchar(10) not null default 'weekly',
+  mission_code varchar(20) not null references missions(code) on update cascade on delete cascade,
+  active_from timestamp,
+  active_until timestamp,
+  created_at timestamp not null default now()
+);
+
+create table quest_steps (
+  id uuid primary key default gen_random_uuid(),
+  quest_id uuid not null references quests(id) on delete cascade,
+  action varchar(10) not null,
+  place_type varchar,
+  target_count integer not null default 1,
+  is_distinct boolean not null default true,
+  created_at timestamp not null default now()
+);
+
+create table user_quests (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid not null references users(id),
+  quest_id uuid not null references quests(id) on delete cascade,
+  period_key varchar(20) not null,
+  progress integer[] not null default '{}',
+  completed_at timestamp,
+  updated_at timestamp not null default now(),
+  unique (user_id, quest_id, period_key)
+);
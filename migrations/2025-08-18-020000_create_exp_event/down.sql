@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+alter table exp_history
+drop column multiplier;
+
+drop table exp_events;
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+create table exp_events (
+  id uuid primary key default gen_random_uuid(),
+  name varchar(50) not null,
+  multiplier float8 not null,
+  mission_code varchar(20) references missions(code) on update cascade on delete cascade,
+  place_type varchar,
+  starts_at timestamp not null,
+  ends_at timestamp not null,
+  created_at timestamp not null default now()
+);
+
+alter table exp_history
+add column multiplier float8 not null default 1;
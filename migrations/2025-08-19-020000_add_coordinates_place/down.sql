@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+drop function distance_meters;
+
+drop index places_latitude_longitude_idx;
+
+alter table places
+drop column latitude,
+drop column longitude;
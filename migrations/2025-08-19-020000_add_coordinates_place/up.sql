@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+alter table places
+add column latitude float8 generated always as ((geometry -> 'location' ->> 'lat')::float8) stored,
+add column longitude float8 generated always as ((geometry -> 'location' ->> 'lng')::float8) stored;
+
+create index places_latitude_longitude_idx on places (latitude, longitude);
+
+-- Great-circle distance in meters between two coordinates.
+create function distance_meters(lat1 float8, lng1 float8, lat2 float8, lng2 float8) returns float8 as $$
+  select 2 * 6371000 * asin(sqrt(
+    power(sin(radians(lat2 - lat1) / 2), 2)
+    + cos(radians(lat1)) * cos(radians(lat2)) * power(sin(radians(lng2 - lng1) / 2), 2)
+  ))
+$$ language sql immutable;
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+drop index places_trending_score_idx;
+
+alter table places
+drop column trending_score;
+
+drop table place_view_buckets;
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+create table place_view_buckets (
+  place_id uuid not null references places(id) on delete cascade,
+  bucket_start timestamp not null,
+  view_count int not null default 0,
+  primary key (place_id, bucket_start)
+);
+
+create index place_view_buckets_bucket_start_idx on place_view_buckets (bucket_start);
+
+alter table places
+add column trending_score float8 not null default 0;
+
+create index places_trending_score_idx on places (trending_score desc);
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+
+alter table places
+add column updated_at timestamp not null default now();
+
+update places set updated_at = created_at;
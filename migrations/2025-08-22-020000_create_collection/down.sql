@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+drop table collection_items;
+
+drop table collections;
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+
+create table collections (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid not null references users(id) on delete cascade,
+  name varchar(100) not null,
+  description text,
+  visibility varchar(10) not null default 'PRIVATE',
+  created_at timestamp not null default now(),
+  updated_at timestamp not null default now()
+);
+
+create index collections_user_id_idx on collections (user_id);
+
+create table collection_items (
+  id uuid primary key default gen_random_uuid(),
+  collection_id uuid not null references collections(id) on delete cascade,
+  place_id uuid not null references places(id) on delete cascade,
+  position int not null,
+  note text,
+  created_at timestamp not null default now(),
+  unique (collection_id, place_id)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+
+drop index user_place_access_user_id_created_at_idx;
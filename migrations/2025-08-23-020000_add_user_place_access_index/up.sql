@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+create index user_place_access_user_id_created_at_idx on user_place_access (user_id, created_at desc);
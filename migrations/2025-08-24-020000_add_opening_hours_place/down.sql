@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+drop function is_open_at;
+
+alter table places
+drop column opening_hours;
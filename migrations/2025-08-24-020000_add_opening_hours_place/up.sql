@@ -0,0 +1,29 @@
+-- Your SQL goes here
+
+alter table places
+add column opening_hours jsonb;
+
+-- Whether a place with Google `opening_hours.periods` and `utc_offset` in minutes is open at the
+-- UTC timestamp `at`, or null when either is unknown. Periods are minutes of the week from Sunday
+-- 00:00; a period without `close` is open around the clock.
+create function is_open_at(opening_hours jsonb, utc_offset text, at timestamp) returns boolean as $$
+  select case
+    when opening_hours is null or utc_offset is null or utc_offset !~ '^-?[0-9]+$' then null
+    else exists (
+      select 1
+      from jsonb_array_elements(opening_hours -> 'periods') as period,
+        lateral (select at + make_interval(mins => utc_offset::int) as local_at) as local_time,
+        lateral (
+          select
+            extract(dow from local_at)::int * 1440 + extract(hour from local_at)::int * 60 + extract(minute from local_at)::int as now_minute,
+            (period -> 'open' ->> 'day')::int * 1440 + substr(period -> 'open' ->> 'time', 1, 2)::int * 60 + substr(period -> 'open' ->> 'time', 3, 2)::int as open_minute,
+            case when jsonb_typeof(period -> 'close') = 'object' then
+              (period -> 'close' ->> 'day')::int * 1440 + substr(period -> 'close' ->> 'time', 1, 2)::int * 60 + substr(period -> 'close' ->> 'time', 3, 2)::int
+            end as close_minute
+        ) as minutes
+      where close_minute is null
+        or (open_minute <= close_minute and now_minute >= open_minute and now_minute < close_minute)
+        or (open_minute > close_minute and (now_minute >= open_minute or now_minute < close_minute))
+    )
+  end
+$$ language sql immutable;
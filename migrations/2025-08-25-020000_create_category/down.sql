@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+-- The re-classification of `user_place_access.type` is not reversible: accesses keep their
+-- category codes, as the Google types they were recorded with are not kept.
+
+drop table category_google_types;
+
+drop table categories;
@@ -0,0 +1,106 @@
+-- Your SQL goes here
+
+create table categories (
+  id uuid primary key default gen_random_uuid(),
+  code varchar(50) unique not null,
+  name varchar(50) not null,
+  parent_code varchar(50) references categories(code) on update cascade,
+  created_at timestamp not null default now()
+);
+
+-- Maps a Google place type to a category. When a place has several mapped types, the mapping
+-- with the highest priority wins, so a coffee shop typed `food` and `cafe` is a café.
+create table category_google_types (
+  google_type varchar(100) primary key,
+  category_code varchar(50) not null references categories(code) on update cascade on delete cascade,
+  priority integer not null default 0
+);
+
+insert into categories (code, name, parent_code) values
+  ('food_and_drink', 'Food & Drink', null),
+  ('shopping', 'Shopping', null),
+  ('entertainment', 'Entertainment', null),
+  ('outdoors', 'Outdoors', null),
+  ('culture', 'Culture', null),
+  ('wellness', 'Health & Wellness', null),
+  ('lodging', 'Lodging', null),
+  ('services', 'Services', null);
+
+insert into categories (code, name, parent_code) values
+  ('cafe', 'Café', 'food_and_drink'),
+  ('restaurant', 'Restaurant', 'food_and_drink'),
+  ('bar', 'Bar', 'food_and_drink'),
+  ('bakery', 'Bakery', 'food_and_drink'),
+  ('grocery', 'Grocery', 'shopping'),
+  ('mall', 'Shopping Mall', 'shopping'),
+  ('store', 'Store', 'shopping'),
+  ('nightlife', 'Nightlife', 'entertainment'),
+  ('cinema', 'Cinema', 'entertainment'),
+  ('amusement', 'Amusement', 'entertainment'),
+  ('park', 'Park', 'outdoors'),
+  ('attraction', 'Attraction', 'outdoors'),
+  ('museum', 'Museum', 'culture'),
+  ('gym', 'Gym', 'wellness'),
+  ('spa', 'Spa & Beauty', 'wellness');
+
+insert into category_google_types (google_type, category_code, priority) values
+  ('food', 'food_and_drink', 0),
+  ('cafe', 'cafe', 100),
+  ('coffee_shop', 'cafe', 100),
+  ('restaurant', 'restaurant', 50),
+  ('meal_takeaway', 'restaurant', 40),
+  ('meal_delivery', 'restaurant', 30),
+  ('bar', 'bar', 80),
+  ('liquor_store', 'store', 20),
+  ('bakery', 'bakery', 90),
+  ('supermarket', 'grocery', 60),
+  ('grocery_or_supermarket', 'grocery', 60),
+  ('convenience_store', 'grocery', 50),
+  ('shopping_mall', 'mall', 70),
+  ('department_store', 'store', 40),
+  ('clothing_store', 'store', 40),
+  ('shoe_store', 'store', 40),
+  ('book_store', 'store', 40),
+  ('electronics_store', 'store', 40),
+  ('jewelry_store', 'store', 40),
+  ('store', 'store', 0),
+  ('night_club', 'nightlife', 90),
+  ('casino', 'nightlife', 80),
+  ('movie_theater', 'cinema', 90),
+  ('amusement_park', 'amusement', 90),
+  ('aquarium', 'amusement', 90),
+  ('bowling_alley', 'amusement', 90),
+  ('zoo', 'amusement', 90),
+  ('park', 'park', 70),
+  ('campground', 'park', 60),
+  ('tourist_attraction', 'attraction', 10),
+  ('museum', 'museum', 90),
+  ('art_gallery', 'museum', 80),
+  ('library', 'culture', 60),
+  ('gym', 'gym', 90),
+  ('spa', 'spa', 90),
+  ('beauty_salon', 'spa', 80),
+  ('hair_care', 'spa', 80),
+  ('lodging', 'lodging', 50),
+  ('laundry', 'services', 50),
+  ('car_repair', 'services', 50),
+  ('bank', 'services', 50),
+  ('pharmacy', 'services', 50);
+
+-- Re-classify recorded accesses under the taxonomy, resolving each place from all of its types as
+-- new accesses are: the mapping with the highest priority wins, the earliest type winning ties.
+update user_place_access
+set type = resolved.category_code
+from (
+  select distinct on (places.id) places.id as place_id, category_google_types.category_code
+  from places
+  cross join lateral unnest(places.types) with ordinality as place_type(google_type, position)
+  join category_google_types on category_google_types.google_type = place_type.google_type
+  order by places.id, category_google_types.priority desc, place_type.position asc
+) as resolved
+where user_place_access.place_id = resolved.place_id;
+
+-- Accesses of places without any mapped type are left unclassified, as new ones are.
+update user_place_access
+set type = ''
+where type not in (select code from categories);
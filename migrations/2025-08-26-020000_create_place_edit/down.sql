@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+drop table place_edit_events;
+
+drop table place_edit_suggestions;
+
+alter table places
+drop column overridden_fields;
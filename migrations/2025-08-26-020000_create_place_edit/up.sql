@@ -0,0 +1,37 @@
+-- Your SQL goes here
+
+alter table places
+add column overridden_fields text[] not null default '{}';
+
+create table place_edit_suggestions (
+  id uuid primary key default gen_random_uuid(),
+  place_id uuid not null references places(id) on delete cascade,
+  user_id uuid not null references users(id),
+  field varchar(30) not null,
+  proposed_value text,
+  reason text,
+  status varchar(10) not null default 'PENDING',
+  reviewed_by uuid references users(id),
+  reviewed_at timestamp,
+  review_note text,
+  created_at timestamp not null default now()
+);
+
+create index place_edit_suggestions_status_created_at_idx on place_edit_suggestions (status, created_at);
+
+-- A user has at most one pending suggestion per field of a place.
+create unique index place_edit_suggestions_pending_idx on place_edit_suggestions (place_id, user_id, field) where status = 'PENDING';
+
+-- Append-only history of suggestions: creation, approval with the replaced value, and rejection.
+create table place_edit_events (
+  id uuid primary key default gen_random_uuid(),
+  suggestion_id uuid not null references place_edit_suggestions(id) on delete cascade,
+  actor_id uuid not null references users(id),
+  action varchar(10) not null,
+  old_value text,
+  new_value text,
+  note text,
+  created_at timestamp not null default now()
+);
+
+create index place_edit_events_suggestion_id_idx on place_edit_events (suggestion_id);
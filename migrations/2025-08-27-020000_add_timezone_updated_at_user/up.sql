@@ -0,0 +1,4 @@
+-- Your SQL goes here
+
+alter table users
+add column timezone_updated_at timestamp;
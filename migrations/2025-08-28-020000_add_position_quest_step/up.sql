@@ -0,0 +1,14 @@
+-- Your SQL goes here
+
+alter table quest_steps
+add column position integer not null default 0;
+
+-- Steps of a quest were inserted together and share their `created_at`, so existing ones are
+-- numbered by id, the only stable order left.
+update quest_steps
+set position = numbered.position
+from (
+  select id, row_number() over (partition by quest_id order by created_at, id) - 1 as position
+  from quest_steps
+) as numbered
+where quest_steps.id = numbered.id;
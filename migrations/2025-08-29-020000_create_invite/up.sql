@@ -0,0 +1,17 @@
+-- Your SQL goes here
+
+-- An accepted invite; `invitee_id` is null for invites accepted before they were recorded, which
+-- are carried over from the `INVITE_FRIEND` EXP ledger.
+create table invites (
+  id uuid primary key default gen_random_uuid(),
+  inviter_id uuid not null references users(id) on delete cascade,
+  invitee_id uuid unique references users(id) on delete set null,
+  created_at timestamp not null default now()
+);
+
+create index invites_inviter_id_idx on invites (inviter_id);
+
+insert into invites (inviter_id, created_at)
+select user_id, created_at
+from exp_history
+where source = 'INVITE_FRIEND' and user_id is not null;
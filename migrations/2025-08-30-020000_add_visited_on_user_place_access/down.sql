@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+-- Duplicate same-day visits deleted by `up.sql` are not restored.
+drop index user_place_access_user_id_place_id_visited_on_idx;
+
+alter table user_place_access
+drop column visited_on;
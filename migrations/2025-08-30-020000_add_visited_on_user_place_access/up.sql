@@ -0,0 +1,26 @@
+-- Your SQL goes here
+
+alter table user_place_access
+add column visited_on date;
+
+-- Local date of each visit in the user's current timezone.
+update user_place_access
+set visited_on = (user_place_access.created_at at time zone 'UTC' at time zone users.timezone)::date
+from users
+where users.id = user_place_access.user_id;
+
+-- Keep only the first visit of a place per day.
+delete from user_place_access
+where id in (
+  select id
+  from (
+    select id, row_number() over (partition by user_id, place_id, visited_on order by created_at, id) as visit_number
+    from user_place_access
+  ) as visits
+  where visit_number > 1
+);
+
+alter table user_place_access
+alter column visited_on set not null;
+
+create unique index user_place_access_user_id_place_id_visited_on_idx on user_place_access (user_id, place_id, visited_on);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+alter table quests
+drop constraint quests_mission_code_fkey,
+add constraint quests_mission_code_fkey foreign key (mission_code) references missions(code) on update cascade on delete cascade;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+alter table check_in_streaks
+drop column started_on;
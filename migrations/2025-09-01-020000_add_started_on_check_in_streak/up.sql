@@ -0,0 +1,9 @@
+-- Your SQL goes here
+
+alter table check_in_streaks
+add column started_on date;
+
+-- Without freeze tokens, the current streak started `current_streak - 1` days before the last check-in.
+update check_in_streaks
+set started_on = last_check_in_date - (greatest(current_streak, 1) - 1)
+where last_check_in_date is not null;
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+    use uuid::Uuid;
+
+    use crate::{
+        models::category::{Category, CategoryGoogleType},
+        services::category::{get_ancestor_codes, get_descendant_codes, resolve_category},
+    };
+
+    fn category(code: &str, parent_code: Option<&str>) -> Category {
+        Category {
+            id: Uuid::new_v4(),
+            code: code.to_string(),
+            name: code.to_string(),
+            parent_code: parent_code.map(String::from),
+            created_at: NaiveDateTime::default(),
+        }
+    }
+
+    fn mapping(google_type: &str, category_code: &str, priority: i32) -> CategoryGoogleType {
+        CategoryGoogleType {
+            google_type: google_type.to_string(),
+            category_code: category_code.to_string(),
+            priority,
+        }
+    }
+
+    fn types(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_highest_priority_mapping_wins_regardless_of_order() {
+        let mappings = vec![mapping("food", "food_and_drink", 0), mapping("restaurant", "restaurant", 50), mapping("cafe", "cafe", 100)];
+
+        assert_eq!(resolve_category(&mappings, &types(&["food", "restaurant", "cafe"])), Some("cafe".to_string()));
+        assert_eq!(resolve_category(&mappings, &types(&["cafe", "food", "restaurant"])), Some("cafe".to_string()));
+    }
+
+    #[test]
+    fn test_unmapped_types_have_no_category() {
+        let mappings = vec![mapping("cafe", "cafe", 100)];
+
+        assert_eq!(resolve_category(&mappings, &types(&["establishment", "point_of_interest"])), None);
+    }
+
+    #[test]
+    fn test_descendants_include_nested_categories() {
+        let categories = vec![
+            category("food_and_drink", None),
+            category("cafe", Some("food_and_drink")),
+            category("bar", Some("food_and_drink")),
+            category("cocktail_bar", Some("bar")),
+            category("park", None),
+        ];
+
+        let mut codes = get_descendant_codes(&categories, "food_and_drink");
+        codes.sort();
+
+        assert_eq!(codes, types(&["bar", "cafe", "cocktail_bar", "food_and_drink"]));
+        assert_eq!(get_descendant_codes(&categories, "laundry"), types(&["laundry"]));
+    }
+
+    #[test]
+    fn test_ancestor_codes_walk_up_to_root() {
+        let categories = vec![
+            category("food_and_drink", None),
+            category("cafe", Some("food_and_drink")),
+            category("restaurant", Some("food_and_drink")),
+        ];
+
+        assert_eq!(get_ancestor_codes(&categories, "cafe"), types(&["cafe", "food_and_drink"]));
+        assert_eq!(get_ancestor_codes(&categories, "coffee_shop"), types(&["coffee_shop"]));
+    }
+}
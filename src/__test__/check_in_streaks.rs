@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use chrono::{NaiveDate, NaiveDateTime};
+    use diesel_async::AsyncConnection;
+    use dotenvy::dotenv;
+    use uuid::Uuid;
+
+    use crate::{
+        config::db::{get_conn, init_pool},
+        models::{
+            check_in_streak::{CheckInStreak, NewCheckInStreak, StreakReward},
+            user::NewUser,
+        },
+        services::{
+            check_in_streak::{advance_streak, get_return_check_in_streak, milestone_rewards, record_check_in, upsert_check_in_streak},
+            user::create_user,
+        },
+    };
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, day).unwrap()
+    }
+
+    fn streak(current_streak: i32, last_day: u32, freeze_tokens: i32) -> CheckInStreak {
+        CheckInStreak {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            current_streak,
+            longest_streak: current_streak,
+            last_check_in_date: Some(date(last_day)),
+            freeze_tokens,
+            created_at: NaiveDateTime::default(),
+            started_on: Some(date(last_day + 1 - current_streak as u32)),
+        }
+    }
+
+    #[test]
+    fn test_first_check_in_starts_streak() {
+        let next = advance_streak(Uuid::nil(), None, date(10));
+
+        assert_eq!(next.current_streak, 1);
+        assert_eq!(next.longest_streak, 1);
+        assert_eq!(next.last_check_in_date, Some(date(10)));
+    }
+
+    #[test]
+    fn test_consecutive_check_in_extends_streak() {
+        let next = advance_streak(Uuid::nil(), Some(&streak(6, 9, 0)), date(10));
+
+        assert_eq!(next.current_streak, 7);
+        assert_eq!(next.longest_streak, 7);
+        assert_eq!(next.started_on, Some(date(4)));
+    }
+
+    #[test]
+    fn test_check_in_before_last_one_keeps_streak() {
+        let next = advance_streak(Uuid::nil(), Some(&streak(6, 10, 0)), date(9));
+
+        assert_eq!(next.current_streak, 6);
+        assert_eq!(next.last_check_in_date, Some(date(10)));
+    }
+
+    #[test]
+    fn test_freeze_tokens_cover_missed_days() {
+        let next = advance_streak(Uuid::nil(), Some(&streak(5, 7, 2)), date(10));
+
+        assert_eq!(next.current_streak, 6);
+        assert_eq!(next.freeze_tokens, 0);
+    }
+
+    #[test]
+    fn test_missed_days_without_freeze_tokens_reset_streak() {
+        let next = advance_streak(Uuid::nil(), Some(&streak(5, 7, 1)), date(10));
+
+        assert_eq!(next.current_streak, 1);
+        assert_eq!(next.longest_streak, 5);
+        assert_eq!(next.freeze_tokens, 1);
+        assert_eq!(next.started_on, Some(date(10)));
+    }
+
+    #[test]
+    fn test_broken_streak_is_reported_as_zero() {
+        assert_eq!(get_return_check_in_streak(Some(&streak(5, 7, 0)), date(8)).current_streak, 5);
+        assert_eq!(get_return_check_in_streak(Some(&streak(5, 7, 0)), date(9)).current_streak, 0);
+    }
+
+    #[test]
+    fn test_milestone_rewards() {
+        assert_eq!(milestone_rewards(7), vec![StreakReward::Exp(50), StreakReward::FreezeToken(1)]);
+        assert_eq!(milestone_rewards(60).len(), 3);
+        assert!(milestone_rewards(8).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_milestone_is_rewarded_once_per_streak() {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+        conn.begin_test_transaction().await.unwrap();
+
+        let user = NewUser {
+            email: "streak@test.com".to_string(),
+            password: "password".to_string(),
+            avatar_url: None,
+            cover_url: None,
+            timezone: None,
+        };
+
+        let user_id = create_user(&mut conn, &user).await.unwrap().id;
+
+        let two_days = NewCheckInStreak {
+            user_id,
+            current_streak: 2,
+            longest_streak: 2,
+            last_check_in_date: Some(date(9)),
+            freeze_tokens: 0,
+            started_on: Some(date(8)),
+        };
+
+        upsert_check_in_streak(&mut conn, &two_days).await.unwrap();
+
+        let (streak, rewards) = record_check_in(&mut conn, user_id, date(10)).await.unwrap();
+        assert_eq!(streak.current_streak, 3);
+        assert_eq!(rewards, vec![StreakReward::Exp(20)]);
+
+        let (_, rewards) = record_check_in(&mut conn, user_id, date(9)).await.unwrap();
+        assert!(rewards.is_empty());
+
+        // The streak is rolled back, e.g. by a stale client, and reaches the milestone again.
+        upsert_check_in_streak(&mut conn, &two_days).await.unwrap();
+
+        let (streak, rewards) = record_check_in(&mut conn, user_id, date(10)).await.unwrap();
+        assert_eq!(streak.current_streak, 3);
+        assert!(rewards.is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+    use uuid::Uuid;
+
+    use crate::models::collection::{Collection, CollectionVisibility};
+
+    fn collection(user_id: Uuid, visibility: CollectionVisibility) -> Collection {
+        Collection {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Hanoi coffee".to_string(),
+            description: None,
+            visibility: visibility.as_str().to_string(),
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_owner_reads_every_collection() {
+        let owner = Uuid::new_v4();
+
+        for visibility in [CollectionVisibility::Public, CollectionVisibility::Private, CollectionVisibility::Unlisted] {
+            assert!(collection(owner, visibility).is_readable_by(owner));
+        }
+    }
+
+    #[test]
+    fn test_other_users_cannot_read_private_collections() {
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        assert!(collection(owner, CollectionVisibility::Public).is_readable_by(other));
+        assert!(collection(owner, CollectionVisibility::Unlisted).is_readable_by(other));
+        assert!(!collection(owner, CollectionVisibility::Private).is_readable_by(other));
+    }
+}
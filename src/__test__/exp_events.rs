@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+    use uuid::Uuid;
+
+    use crate::{models::exp_event::ExpEvent, services::exp_event::get_exp_multiplier};
+
+    fn event(multiplier: f64, mission_code: Option<&str>, place_type: Option<&str>) -> ExpEvent {
+        ExpEvent {
+            id: Uuid::new_v4(),
+            name: "Event".to_string(),
+            multiplier,
+            mission_code: mission_code.map(String::from),
+            place_type: place_type.map(String::from),
+            starts_at: NaiveDateTime::default(),
+            ends_at: NaiveDateTime::default(),
+            created_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_no_events_keeps_exp() {
+        assert_eq!(get_exp_multiplier(&[], "REVIEW_PLACE", &[]), 1.0);
+    }
+
+    #[test]
+    fn test_global_events_stack() {
+        let events = vec![event(2.0, None, None), event(1.5, None, None)];
+
+        assert_eq!(get_exp_multiplier(&events, "DAILY_CHECK_IN", &[]), 3.0);
+    }
+
+    #[test]
+    fn test_filtered_events_only_apply_to_matches() {
+        let events = vec![event(3.0, Some("REVIEW_PLACE"), Some("restaurant")), event(2.0, None, None)];
+
+        let restaurant = vec!["restaurant".to_string(), "food".to_string()];
+        let cafe = vec!["cafe".to_string()];
+
+        assert_eq!(get_exp_multiplier(&events, "REVIEW_PLACE", &restaurant), 6.0);
+        assert_eq!(get_exp_multiplier(&events, "REVIEW_PLACE", &cafe), 2.0);
+        assert_eq!(get_exp_multiplier(&events, "UPLOAD_PHOTO", &restaurant), 2.0);
+    }
+
+    #[test]
+    fn test_category_events_apply_to_tagged_places() {
+        let events = vec![event(2.0, None, Some("food_and_drink"))];
+
+        let cafe = vec!["cafe".to_string(), "food".to_string(), "cafe".to_string(), "food_and_drink".to_string()];
+        let museum = vec!["museum".to_string(), "culture".to_string()];
+
+        assert_eq!(get_exp_multiplier(&events, "VISIT_PLACE", &cafe), 2.0);
+        assert_eq!(get_exp_multiplier(&events, "VISIT_PLACE", &museum), 1.0);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+
+    use crate::{
+        models::level::{DEFAULT_EXP_REQUIRED, Level, LevelUp},
+        services::level::{exp_required_for, level_progress, resolve_level_up},
+    };
+
+    fn curve() -> Vec<Level> {
+        (1..=3)
+            .map(|level| Level {
+                level,
+                exp_required: 100 * level,
+                reward_type: None,
+                reward_count: None,
+                created_at: NaiveDateTime::default(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_levels_past_the_curve_reuse_last_threshold() {
+        let levels = curve();
+
+        assert_eq!(exp_required_for(&levels, 2), 200);
+        assert_eq!(exp_required_for(&levels, 10), 300);
+        assert_eq!(exp_required_for(&[], 1), DEFAULT_EXP_REQUIRED);
+    }
+
+    #[test]
+    fn test_resolve_multiple_level_ups() {
+        let levels = curve();
+
+        let result = resolve_level_up(&levels, 1, 350);
+
+        assert_eq!(
+            result,
+            LevelUp {
+                level: 3,
+                exp: 50,
+                reached: vec![2, 3],
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_without_level_up() {
+        let levels = curve();
+
+        let result = resolve_level_up(&levels, 2, 199);
+
+        assert_eq!(result.level, 2);
+        assert!(result.reached.is_empty());
+    }
+
+    #[test]
+    fn test_level_progress() {
+        let levels = curve();
+
+        let progress = level_progress(&levels, 2, 50);
+
+        assert_eq!(progress.exp_required, 200);
+        assert_eq!(progress.exp_to_next_level, 150);
+        assert_eq!(progress.progress, 0.25);
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use validator::Validate;
+
+    use crate::handlers::mission::UpdateMissionPayload;
+
+    #[test]
+    fn test_missing_fields_are_left_unchanged() {
+        let payload: UpdateMissionPayload = serde_json::from_value(json!({ "name": "Check in" })).unwrap();
+
+        assert_eq!(payload.max_per_day, None);
+        assert_eq!(payload.gift_reward_type, None);
+    }
+
+    #[test]
+    fn test_null_fields_are_cleared() {
+        let payload: UpdateMissionPayload = serde_json::from_value(json!({ "max_per_day": null, "gift_reward_type": null })).unwrap();
+
+        assert_eq!(payload.max_per_day, Some(None));
+        assert_eq!(payload.gift_reward_type, Some(None));
+        assert!(payload.validate().is_ok());
+    }
+
+    #[test]
+    fn test_set_fields_are_updated() {
+        let payload: UpdateMissionPayload = serde_json::from_value(json!({ "max_per_day": 3 })).unwrap();
+
+        assert_eq!(payload.max_per_day, Some(Some(3)));
+    }
+
+    #[test]
+    fn test_set_fields_are_validated() {
+        let payload: UpdateMissionPayload = serde_json::from_value(json!({ "max_per_day": 0 })).unwrap();
+
+        assert!(payload.validate().is_err());
+    }
+}
@@ -1 +1,17 @@
+mod categories;
+mod check_in_streaks;
+mod collections;
+mod exp_events;
+mod levels;
+mod missions;
+mod notifications;
+mod opening_hours;
+mod pagination;
+mod place_edits;
+mod places;
+mod recommendations;
+mod reviews;
+mod subscriptions;
+mod timezones;
+mod visits;
 mod waypoints;
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod test {
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use chrono::NaiveTime;
+    use dotenvy::dotenv;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::{config::app::init_test_app, services::notification_preference::is_email_preference_allowed, utils::jwt::sign_unsubscribe_token};
+
+    fn time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_disabled_email_preference_blocks_category() {
+        assert!(!is_email_preference_allowed(Some(false), "mission", None, time(12)));
+        assert!(!is_email_preference_allowed(Some(false), "account", None, time(12)));
+        assert!(is_email_preference_allowed(None, "mission", None, time(12)));
+    }
+
+    #[test]
+    fn test_quiet_hours_hold_all_but_account_emails() {
+        let quiet_hours = Some((time(22), time(7)));
+
+        assert!(!is_email_preference_allowed(Some(true), "marketing", quiet_hours, time(23)));
+        assert!(is_email_preference_allowed(Some(true), "account", quiet_hours, time(23)));
+        assert!(is_email_preference_allowed(Some(true), "marketing", quiet_hours, time(12)));
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_link_only_asks_for_confirmation() {
+        dotenv().ok();
+
+        let app = init_test_app().await;
+
+        let token = sign_unsubscribe_token("friend@gmail.com".to_string(), "invite".to_string()).unwrap();
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/notifications/unsubscribe?token={}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body.contains(r#"<form method="post""#));
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_rejects_invalid_token() {
+        dotenv().ok();
+
+        let app = init_test_app().await;
+
+        for method in [Method::GET, Method::POST] {
+            let request = Request::builder().method(method).uri("/notifications/unsubscribe?token=invalid").body(Body::empty()).unwrap();
+
+            let response = app.clone().oneshot(request).await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+    use diesel_async::RunQueryDsl;
+    use dotenvy::dotenv;
+    use serde_json::{Value, json};
+
+    use crate::{
+        config::db::{get_conn, init_pool},
+        models::opening_hours::OpeningHours,
+        services::place::is_open_at,
+    };
+
+    /// 2025-08-24 is a Sunday.
+    fn local(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, 24 + day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_open_within_period() {
+        let opening_hours = OpeningHours::from_google(&json!({
+            "open_now": true,
+            "periods": [{ "open": { "day": 1, "time": "0800" }, "close": { "day": 1, "time": "1730" } }]
+        }))
+        .unwrap();
+
+        assert!(!opening_hours.is_open_at(local(1, 7, 59)));
+        assert!(opening_hours.is_open_at(local(1, 8, 0)));
+        assert!(!opening_hours.is_open_at(local(1, 17, 30)));
+        assert!(!opening_hours.is_open_at(local(2, 9, 0)));
+    }
+
+    #[test]
+    fn test_period_wraps_around_the_week() {
+        let opening_hours = OpeningHours::from_google(&json!({
+            "periods": [{ "open": { "day": 6, "time": "2200" }, "close": { "day": 0, "time": "0200" } }]
+        }))
+        .unwrap();
+
+        assert!(opening_hours.is_open_at(local(6, 23, 0)));
+        assert!(opening_hours.is_open_at(local(0, 1, 0)));
+        assert!(!opening_hours.is_open_at(local(0, 3, 0)));
+    }
+
+    #[test]
+    fn test_period_without_close_is_always_open() {
+        let opening_hours = OpeningHours::from_google(&json!({
+            "periods": [{ "open": { "day": 0, "time": "0000" } }]
+        }))
+        .unwrap();
+
+        assert!(opening_hours.is_open_at(local(3, 4, 0)));
+    }
+
+    #[test]
+    fn test_invalid_hours_are_rejected() {
+        assert!(OpeningHours::from_google(&json!({ "periods": [] })).is_none());
+        assert!(OpeningHours::from_google(&json!({ "periods": [{ "open": { "day": 7, "time": "0800" } }] })).is_none());
+        assert!(OpeningHours::from_google(&json!({ "periods": [{ "open": { "day": 1, "time": "8:00" } }] })).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sql_matches_rust_implementation() {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+
+        let weekday = json!({ "periods": [{ "open": { "day": 1, "time": "0800" }, "close": { "day": 1, "time": "1730" } }] });
+        let wrap_around = json!({ "periods": [{ "open": { "day": 6, "time": "2200" }, "close": { "day": 0, "time": "0200" } }] });
+        let no_close = json!({ "periods": [{ "open": { "day": 0, "time": "0000" } }] });
+
+        let cases: Vec<(&Value, NaiveDateTime, bool)> = vec![
+            (&weekday, local(1, 7, 59), false),
+            (&weekday, local(1, 8, 0), true),
+            (&weekday, local(1, 17, 29), true),
+            (&weekday, local(1, 17, 30), false),
+            (&weekday, local(2, 9, 0), false),
+            (&wrap_around, local(6, 21, 59), false),
+            (&wrap_around, local(6, 22, 0), true),
+            (&wrap_around, local(0, 1, 59), true),
+            (&wrap_around, local(0, 2, 0), false),
+            (&no_close, local(3, 4, 0), true),
+        ];
+
+        // UTC+07:00, so that the local time is on another day than the UTC time for early hours.
+        let utc_offset = 420;
+
+        for (opening_hours, local, expected) in cases {
+            let rust_open = OpeningHours::from_google(opening_hours).unwrap().is_open_at(local);
+
+            let sql_open: Option<bool> = diesel::select(is_open_at(Some(opening_hours.clone()), Some(utc_offset.to_string()), local - TimeDelta::minutes(utc_offset)))
+                .get_result(&mut conn)
+                .await
+                .unwrap();
+
+            assert_eq!(rust_open, expected, "Rust at {}", local);
+            assert_eq!(sql_open, Some(expected), "SQL at {}", local);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sql_unknown_hours_are_null() {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+
+        let no_hours: Option<bool> = diesel::select(is_open_at(None::<Value>, Some("420"), local(1, 9, 0))).get_result(&mut conn).await.unwrap();
+        let no_offset: Option<bool> = diesel::select(is_open_at(Some(json!({ "periods": [] })), None::<String>, local(1, 9, 0)))
+            .get_result(&mut conn)
+            .await
+            .unwrap();
+
+        assert_eq!(no_hours, None);
+        assert_eq!(no_offset, None);
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod test {
+    use validator::Validate;
+
+    use crate::utils::pagination::PaginationQuery;
+
+    #[test]
+    fn test_offset_skips_previous_pages() {
+        let pagination = PaginationQuery { page: Some(3), limit: Some(20) };
+
+        assert_eq!(pagination.offset(), 40);
+    }
+
+    #[test]
+    fn test_huge_page_is_rejected() {
+        let pagination = PaginationQuery {
+            page: Some(i64::MAX),
+            limit: Some(100),
+        };
+
+        assert!(pagination.validate().is_err());
+    }
+
+    #[test]
+    fn test_huge_page_offset_does_not_overflow() {
+        let pagination = PaginationQuery {
+            page: Some(i64::MAX),
+            limit: Some(100),
+        };
+
+        assert_eq!(pagination.offset(), i64::MAX);
+    }
+}
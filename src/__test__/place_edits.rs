@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod test {
+    use crate::models::place_edit::PlaceEditField;
+
+    #[test]
+    fn test_field_names_round_trip() {
+        for name in ["name", "formatted_address", "formatted_phone_number", "business_status", "website"] {
+            assert_eq!(PlaceEditField::parse(name).map(|field| field.as_str()), Some(name));
+        }
+
+        assert_eq!(PlaceEditField::parse("rating"), None);
+    }
+
+    #[test]
+    fn test_only_name_cannot_be_cleared() {
+        assert!(!PlaceEditField::Name.is_valid_value(None));
+        assert!(!PlaceEditField::Name.is_valid_value(Some("  ")));
+        assert!(PlaceEditField::Website.is_valid_value(None));
+        assert!(PlaceEditField::FormattedPhoneNumber.is_valid_value(Some("+84 24 3825 1234")));
+    }
+
+    #[test]
+    fn test_business_status_must_be_known() {
+        assert!(PlaceEditField::BusinessStatus.is_valid_value(Some("CLOSED_PERMANENTLY")));
+        assert!(!PlaceEditField::BusinessStatus.is_valid_value(Some("closed")));
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+    use diesel::{ExpressionMethods, QueryDsl, TextExpressionMethods};
+    use diesel_async::{AsyncConnection, RunQueryDsl};
+    use dotenvy::dotenv;
+
+    use crate::{
+        config::db::{get_conn, init_pool},
+        models::place::{NewPlace, PlaceFilter},
+        schema::places,
+        services::place::{create_place, get_longitude_ranges, get_nearby_places, get_trending_places, is_place_stale},
+    };
+
+    fn time() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, 21).unwrap().and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_recent_place_is_not_stale() {
+        assert!(!is_place_stale(time(), time() + TimeDelta::hours(23)));
+    }
+
+    #[test]
+    fn test_old_place_is_stale() {
+        assert!(is_place_stale(time(), time() + TimeDelta::hours(24)));
+    }
+
+    #[test]
+    fn test_older_data_does_not_refresh() {
+        assert!(!is_place_stale(time(), time() - TimeDelta::days(3)));
+    }
+
+    #[test]
+    fn test_longitude_range_away_from_antimeridian() {
+        assert_eq!(get_longitude_ranges(108.0, 1.0), ((107.0, 109.0), None));
+    }
+
+    #[test]
+    fn test_longitude_range_splits_across_antimeridian_east() {
+        assert_eq!(get_longitude_ranges(179.5, 1.0), ((178.5, 180.0), Some((-180.0, -179.5))));
+    }
+
+    #[test]
+    fn test_longitude_range_splits_across_antimeridian_west() {
+        assert_eq!(get_longitude_ranges(-179.5, 1.0), ((179.5, 180.0), Some((-180.0, -178.5))));
+    }
+
+    #[test]
+    fn test_longitude_range_covers_whole_circle() {
+        assert_eq!(get_longitude_ranges(10.0, 180.0), ((-180.0, 180.0), None));
+    }
+
+    fn new_place(place_id: &str, lat: f64, lng: f64) -> NewPlace {
+        serde_json::from_value(serde_json::json!({
+            "place_id": place_id,
+            "name": place_id,
+            "geometry": {
+                "location": { "lat": lat, "lng": lng },
+                "viewport": {
+                    "northeast": { "lat": lat, "lng": lng },
+                    "southwest": { "lat": lat, "lng": lng }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_nearby_places_across_antimeridian() {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+        conn.begin_test_transaction().await.unwrap();
+
+        // 0.001 degree of longitude on the equator is about 111 meters.
+        create_place(&mut conn, &new_place("test_antimeridian_east", 0.0, 179.9995)).await.unwrap();
+        create_place(&mut conn, &new_place("test_antimeridian_west", 0.0, -179.9995)).await.unwrap();
+        create_place(&mut conn, &new_place("test_antimeridian_far", 0.0, -179.99)).await.unwrap();
+
+        let (places, total) = get_nearby_places(&mut conn, 0.0, 179.9995, 500.0, PlaceFilter::default(), 10, 0).await.unwrap();
+
+        let place_ids: Vec<&str> = places.iter().map(|(place, _)| place.place_id.as_str()).collect();
+        assert_eq!(place_ids, vec!["test_antimeridian_east", "test_antimeridian_west"]);
+        assert_eq!(total, 2);
+        assert!(places[0].1 < 1.0);
+        assert!((places[1].1 - 111.3).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_trending_places_across_antimeridian() {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+        conn.begin_test_transaction().await.unwrap();
+
+        create_place(&mut conn, &new_place("test_trending_east", 0.0, 179.9995)).await.unwrap();
+        create_place(&mut conn, &new_place("test_trending_west", 0.0, -179.9995)).await.unwrap();
+        create_place(&mut conn, &new_place("test_trending_far", 0.0, -179.99)).await.unwrap();
+
+        diesel::update(places::table.filter(places::place_id.like("test_trending_%")))
+            .set(places::trending_score.eq(1.0))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let (places, total) = get_trending_places(&mut conn, Some((0.0, 179.9995, 500.0)), PlaceFilter::default(), 10, 0).await.unwrap();
+
+        let mut place_ids: Vec<&str> = places.iter().map(|place| place.place_id.as_str()).collect();
+        place_ids.sort();
+        assert_eq!(place_ids, vec!["test_trending_east", "test_trending_west"]);
+        assert_eq!(total, 2);
+    }
+}
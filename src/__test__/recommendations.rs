@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDateTime;
+    use uuid::Uuid;
+
+    use crate::{
+        models::{category::Category, recommendation::RecommendationCandidate},
+        services::recommendation::score_place,
+    };
+
+    fn category(code: &str, name: &str) -> Category {
+        Category {
+            id: Uuid::new_v4(),
+            code: code.to_string(),
+            name: name.to_string(),
+            parent_code: None,
+            created_at: NaiveDateTime::default(),
+        }
+    }
+
+    fn candidate(category: Option<&Category>, rating: Option<f64>, distance: f64) -> RecommendationCandidate<'_> {
+        RecommendationCandidate {
+            category,
+            rating,
+            user_ratings_total: Some(100),
+            trending_score: 0.0,
+            distance,
+        }
+    }
+
+    #[test]
+    fn test_affinity_explains_recommendation() {
+        let affinities = HashMap::from([("cafe".to_string(), 0.8)]);
+
+        let (_, reason) = score_place(&affinities, &candidate(Some(&category("cafe", "Café")), Some(4.5), 100.0), 1000.0);
+
+        assert_eq!(reason, "Because you visit Café places");
+    }
+
+    #[test]
+    fn test_preferred_category_outscores_other_category() {
+        let affinities = HashMap::from([("cafe".to_string(), 0.8), ("nightlife".to_string(), 0.2)]);
+
+        let (cafe_score, _) = score_place(&affinities, &candidate(Some(&category("cafe", "Café")), Some(4.0), 500.0), 1000.0);
+        let (club_score, _) = score_place(&affinities, &candidate(Some(&category("nightlife", "Nightlife")), Some(4.0), 500.0), 1000.0);
+
+        assert!(cafe_score > club_score);
+    }
+
+    #[test]
+    fn test_closer_place_outscores_farther_place() {
+        let affinities = HashMap::new();
+
+        let (near_score, reason) = score_place(&affinities, &candidate(None, Some(4.0), 100.0), 1000.0);
+        let (far_score, _) = score_place(&affinities, &candidate(None, Some(4.0), 900.0), 1000.0);
+
+        assert!(near_score > far_score);
+        assert_eq!(reason, "Popular near you");
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use diesel_async::AsyncConnection;
+    use dotenvy::dotenv;
+
+    use crate::{
+        config::db::{get_conn, init_pool},
+        models::{place::NewPlace, review::NewReview, user::NewUser},
+        services::{
+            place::create_place,
+            review::{create_review, get_review_stats},
+            user::create_user,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_review_stats_ignore_ratings_out_of_range() {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+        conn.begin_test_transaction().await.unwrap();
+
+        let user = NewUser {
+            email: "reviewer@test.com".to_string(),
+            password: "password".to_string(),
+            avatar_url: None,
+            cover_url: None,
+            timezone: None,
+        };
+        let user_id = create_user(&mut conn, &user).await.unwrap().id;
+
+        let place: NewPlace = serde_json::from_value(serde_json::json!({ "place_id": "test_reviewed_place", "name": "Reviewed place" })).unwrap();
+        let place_id = create_place(&mut conn, &place).await.unwrap().id;
+
+        for rating in [0.0, 4.0, 5.0] {
+            let review = NewReview {
+                user_id: Some(user_id),
+                place_id,
+                author_name: None,
+                author_url: None,
+                language: None,
+                profile_photo_url: None,
+                rating,
+                relative_time_description: None,
+                text: "Review".to_string(),
+                time: None,
+                medias: None,
+            };
+
+            create_review(&mut conn, &review).await.unwrap();
+        }
+
+        let stats = get_review_stats(&mut conn, place_id).await.unwrap();
+
+        assert_eq!(stats.review_count, 2);
+        assert_eq!(stats.rating_histogram, [0, 0, 0, 1, 1]);
+        assert_eq!(stats.average_rating, Some(4.5));
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+
+    use crate::services::subscription::get_gift_end_date;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, 10).unwrap().and_hms_opt(9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_gifts_stack_back_to_back() {
+        let first_end = get_gift_end_date(None, now(), 7);
+        let second_end = get_gift_end_date(Some(first_end), now() + TimeDelta::hours(1), 7);
+
+        assert_eq!(first_end, now() + TimeDelta::days(7));
+        assert_eq!(second_end, now() + TimeDelta::days(14));
+    }
+
+    #[test]
+    fn test_gift_after_expiry_starts_now() {
+        assert_eq!(get_gift_end_date(Some(now() - TimeDelta::days(1)), now(), 3), now() + TimeDelta::days(3));
+    }
+}
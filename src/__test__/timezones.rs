@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use crate::utils::time::{is_date_moved_back, is_timezone_change_allowed};
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, 15).unwrap().and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_timezone_change_cooldown() {
+        assert!(is_timezone_change_allowed(None, now()));
+        assert!(!is_timezone_change_allowed(Some(now() - TimeDelta::days(6)), now()));
+        assert!(is_timezone_change_allowed(Some(now() - TimeDelta::days(7)), now()));
+    }
+
+    #[test]
+    fn test_hop_across_date_line() {
+        // 12:00 UTC is 02:00 on the next day in Kiritimati and 02:00 on the same day in Honolulu.
+        let now = Utc.from_utc_datetime(&now());
+
+        assert!(is_date_moved_back(Tz::Pacific__Kiritimati, Tz::Pacific__Honolulu, now));
+        assert!(!is_date_moved_back(Tz::Pacific__Honolulu, Tz::Pacific__Kiritimati, now));
+        assert!(!is_date_moved_back(Tz::Asia__Ho_Chi_Minh, Tz::Europe__London, now));
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use chrono::{NaiveDate, NaiveDateTime};
+    use chrono_tz::Tz;
+    use diesel_async::AsyncConnection;
+    use dotenvy::dotenv;
+    use uuid::Uuid;
+
+    use crate::{
+        config::db::{DbConn, get_conn, init_pool},
+        models::{
+            place::NewPlace,
+            quest::{QuestAction, QuestStep},
+            user::NewUser,
+            user_place_access::NewUserPlaceAccess,
+        },
+        services::{
+            place::create_place,
+            quest::get_step_progress,
+            user::create_user,
+            user_place_access::{create_user_place_access, get_visit_stats},
+        },
+    };
+
+    async fn test_conn() -> DbConn {
+        dotenv().ok();
+
+        let pool = init_pool(&env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST is missing.")).unwrap();
+        let mut conn = get_conn(&pool).await.unwrap();
+        conn.begin_test_transaction().await.unwrap();
+
+        conn
+    }
+
+    async fn visit(conn: &mut DbConn) -> NewUserPlaceAccess {
+        let user = NewUser {
+            email: "visitor@test.com".to_string(),
+            password: "password".to_string(),
+            avatar_url: None,
+            cover_url: None,
+            timezone: None,
+        };
+
+        let place: NewPlace = serde_json::from_value(serde_json::json!({ "place_id": "test_visited_place", "name": "Visited place" })).unwrap();
+
+        NewUserPlaceAccess {
+            user_id: create_user(conn, &user).await.unwrap().id,
+            place_id: create_place(conn, &place).await.unwrap().id,
+            type_: "cafe".to_string(),
+            visited_on: NaiveDate::from_ymd_opt(2025, 8, 30).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_same_day_visit_is_recorded_once() {
+        let mut conn = test_conn().await;
+        let visit = visit(&mut conn).await;
+
+        assert!(create_user_place_access(&mut conn, &visit).await.unwrap().is_some());
+        assert!(create_user_place_access(&mut conn, &visit).await.unwrap().is_none());
+
+        let next_day = NewUserPlaceAccess {
+            visited_on: visit.visited_on.succ_opt().unwrap(),
+            ..visit
+        };
+
+        assert!(create_user_place_access(&mut conn, &next_day).await.unwrap().is_some());
+    }
+
+    fn visit_step(is_distinct: bool) -> QuestStep {
+        QuestStep {
+            id: Uuid::new_v4(),
+            quest_id: Uuid::new_v4(),
+            action: QuestAction::Visit.as_str().to_string(),
+            place_type: None,
+            target_count: 3,
+            is_distinct,
+            created_at: NaiveDateTime::default(),
+            position: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_same_day_visits_count_once_towards_quest_steps() {
+        let mut conn = test_conn().await;
+        let visit = visit(&mut conn).await;
+        let user_id = visit.user_id;
+
+        create_user_place_access(&mut conn, &visit).await.unwrap();
+        create_user_place_access(&mut conn, &visit).await.unwrap();
+
+        assert_eq!(get_step_progress(&mut conn, user_id, &visit_step(false), None).await.unwrap(), 1);
+
+        let next_day = NewUserPlaceAccess {
+            visited_on: visit.visited_on.succ_opt().unwrap(),
+            ..visit
+        };
+
+        create_user_place_access(&mut conn, &next_day).await.unwrap();
+
+        assert_eq!(get_step_progress(&mut conn, user_id, &visit_step(false), None).await.unwrap(), 2);
+        assert_eq!(get_step_progress(&mut conn, user_id, &visit_step(true), None).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_same_day_visits_count_once_in_stats() {
+        let mut conn = test_conn().await;
+        let visit = visit(&mut conn).await;
+        let user_id = visit.user_id;
+
+        let next_day = NewUserPlaceAccess {
+            visited_on: visit.visited_on.succ_opt().unwrap(),
+            type_: visit.type_.clone(),
+            ..visit
+        };
+
+        create_user_place_access(&mut conn, &visit).await.unwrap();
+        create_user_place_access(&mut conn, &visit).await.unwrap();
+        create_user_place_access(&mut conn, &next_day).await.unwrap();
+
+        let stats = get_visit_stats(&mut conn, user_id, Tz::UTC).await.unwrap();
+
+        assert_eq!(stats.total_visits, 2);
+        assert_eq!(stats.distinct_places, 1);
+        assert_eq!(stats.by_type.iter().map(|count| (count.type_.as_str(), count.visit_count)).collect::<Vec<_>>(), vec![("cafe", 2)]);
+        assert_eq!(stats.most_visited.iter().map(|place| place.visit_count).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(stats.monthly.iter().map(|month| month.visit_count).sum::<i64>(), 2);
+    }
+}
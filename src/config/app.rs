@@ -1,6 +1,11 @@
 use std::env;
 
-use crate::routes::{auth::auth_routes, iap::iap_routes, mission::mission_routes, place::place_routes, review::review_routes, upload::upload_routes, user::user_routes, waypoint::waypoint_routes};
+use crate::jobs::trending::spawn_trending_job;
+use crate::routes::{
+    achievement::achievement_routes, auth::auth_routes, category::category_routes, collection::collection_routes, exp_event::exp_event_routes, iap::iap_routes, leaderboard::leaderboard_routes,
+    mission::mission_routes, notification::notification_routes, place::place_routes, place_edit::place_edit_routes, quest::quest_routes, review::review_routes, upload::upload_routes,
+    user::user_routes, waypoint::waypoint_routes,
+};
 use axum::{Extension, Router};
 use tokio::net::TcpListener;
 use tower_http::trace::{DefaultMakeSpan, DefaultOnFailure, DefaultOnResponse, TraceLayer};
@@ -15,14 +20,24 @@ async fn init_app(db_url: &str, cache_url: &str, mailer_username: &str, mailer_p
 
     let mailer = init_mailer(mailer_username, mailer_password, mailer_relay_mail);
 
+    spawn_trending_job(pool.clone());
+
     Router::new()
         .nest("/auth", auth_routes())
         .nest("/waypoints", waypoint_routes())
         .nest("/iap", iap_routes())
         .nest("/places", place_routes())
+        .nest("/place-suggestions", place_edit_routes())
+        .nest("/categories", category_routes())
         .nest("/reviews", review_routes())
+        .nest("/collections", collection_routes())
         .nest("/missions", mission_routes())
+        .nest("/achievements", achievement_routes())
+        .nest("/exp-events", exp_event_routes())
+        .nest("/leaderboards", leaderboard_routes())
+        .nest("/quests", quest_routes())
         .nest("/users", user_routes())
+        .nest("/notifications", notification_routes())
         .nest("/uploads", upload_routes())
         .layer(
             TraceLayer::new_for_http()
@@ -1,9 +1,50 @@
 use lettre::{
     Message, SmtpTransport, Transport,
-    message::{Mailbox, header::ContentType},
+    message::{
+        Mailbox,
+        header::{ContentType, Header, HeaderName, HeaderValue},
+    },
     transport::smtp::{authentication::Credentials, response::Response},
 };
-use std::env;
+use std::{env, error::Error};
+
+use crate::{config::db::DbConn, services::notification_preference::is_email_allowed};
+
+/// `List-Unsubscribe` header, lets mail clients show their own unsubscribe button.
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(Self(s.trim_start_matches('<').trim_end_matches('>').to_owned()))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), format!("<{}>", self.0))
+    }
+}
+
+/// `List-Unsubscribe-Post` header, marks the unsubscribe link as one-click (RFC 8058).
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+    }
+
+    fn parse(_: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(Self)
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_owned())
+    }
+}
 
 pub fn init_mailer(username: &str, password: &str, relay_mail: &str) -> SmtpTransport {
     let creds = Credentials::new(username.to_owned(), password.to_owned());
@@ -11,18 +52,37 @@ pub fn init_mailer(username: &str, password: &str, relay_mail: &str) -> SmtpTran
     SmtpTransport::relay(relay_mail).unwrap().credentials(creds).build()
 }
 
-pub fn mailer_send(mailer: &SmtpTransport, mail: &Message) -> Result<Response, String> {
-    mailer.send(mail).map_err(|err| err.to_string())
+/// Sends `mail` unless one of its recipients opted out of `category` emails.
+///
+/// # Returns
+/// - `Ok(Some(response))` if the mail was sent.
+/// - `Ok(None)` if the mail was suppressed by the recipient's notification preferences.
+/// - `Err(String)` if the preferences could not be read or the mail failed to send.
+///
+pub async fn mailer_send(conn: &mut DbConn, mailer: &SmtpTransport, mail: &Message, category: &str) -> Result<Option<Response>, String> {
+    for recipient in mail.envelope().to() {
+        let allowed = is_email_allowed(conn, recipient.as_ref(), category).await.map_err(|err| err.to_string())?;
+
+        if !allowed {
+            return Ok(None);
+        }
+    }
+
+    mailer.send(mail).map(Some).map_err(|err| err.to_string())
 }
 
-pub fn mail_template(to_mail: &str, body: &str) -> Result<Message, String> {
+pub fn mail_template(to_mail: &str, body: &str, unsubscribe_link: Option<&str>) -> Result<Message, String> {
     let from_mail = env::var("MAILER_FROM_MAIL").map_err(|err| err.to_string())?;
 
-    Ok(Message::builder()
+    let mut builder = Message::builder()
         .from(Mailbox::new(Some("Wow".to_owned()), from_mail.as_str().parse().unwrap()))
         .to(Mailbox::new(Some(to_mail.to_owned()), to_mail.parse().unwrap()))
         .subject("You've Been Invited to Wow App")
-        .header(ContentType::TEXT_HTML)
-        .body(body.to_string())
-        .unwrap())
+        .header(ContentType::TEXT_HTML);
+
+    if let Some(link) = unsubscribe_link {
+        builder = builder.header(ListUnsubscribe(link.to_owned())).header(ListUnsubscribePost);
+    }
+
+    Ok(builder.body(body.to_string()).unwrap())
 }
@@ -0,0 +1,42 @@
+use axum::{Extension, Json};
+use serde_json::{Value, json};
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    models::{achievement::ReturnAchievement, user::User},
+    services::achievement::{get_achievements, get_achievements_progress, get_user_achievements},
+    utils::error_handling::AppError,
+};
+
+pub async fn search_achievements(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let achievements = get_achievements(&mut conn).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let unlocked = get_user_achievements(&mut conn, current_user.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let progress = get_achievements_progress(&mut conn, current_user.id, &achievements)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let mut results = Vec::with_capacity(achievements.len());
+
+    for achievement in achievements {
+        let progress = progress.get(&achievement.id).copied().unwrap_or(0);
+
+        let unlocked_at = unlocked
+            .iter()
+            .find(|(user_achievement, _)| user_achievement.achievement_id == achievement.id)
+            .map(|(user_achievement, _)| user_achievement.unlocked_at);
+
+        results.push(ReturnAchievement {
+            progress: progress.min(achievement.threshold as i64),
+            achievement,
+            unlocked_at,
+        });
+    }
+
+    Ok(Json(json!({
+        "achievements": results
+    })))
+}
@@ -0,0 +1,3 @@
+mod logic;
+
+pub use logic::*;
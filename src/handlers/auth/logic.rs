@@ -15,13 +15,19 @@ use crate::{
     },
     handlers::auth::{CheckValidUserQuery, ReturnFeatureUsage, ReturnUser, SignInPayload, SignUpPayload},
     models::{
+        achievement::AchievementEvent,
         action_count::NewActionCount,
         feature_usage::NewFeatureUsage,
+        invite::NewInvite,
         user::{NewUser, User},
     },
     services::{
+        achievement::evaluate_achievements,
         action_count::create_action_count,
+        check_in_streak::{get_check_in_streak_by_user, get_return_check_in_streak},
         feature_usage::{create_feature_usage, get_feature_usage_by_user},
+        invite::create_invite,
+        level::{get_levels, level_progress},
         mission::do_mission,
         user::{create_user, get_user_by_email},
     },
@@ -30,29 +36,47 @@ use crate::{
         error_handling::AppError,
         hash::{hash_password, verify_password},
         jwt::{sign_token, verify_token},
+        time::{get_today_date, get_user_timezone, parse_timezone},
     },
 };
 
-async fn response_invite<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, code: &str) -> Result<(), AppError> {
+/// Records the sign-up of `invitee_id` through the invite `code`, then rewards the inviter. The
+/// invite counts towards the inviter's achievements even when the reward mission is capped or
+/// disabled.
+async fn response_invite<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, code: &str, invitee_id: Uuid) -> Result<(), AppError> {
     let key = format!("invite:{}", code);
 
     let inviter_id: String = cache_conn.get(&key).await.map_err(|e| AppError::BadRequest(format!("Redis error: {}", e)))?;
 
-    do_mission(conn, cache_conn, &inviter_id, "INVITE_FRIEND", None)
-        .await
-        .map_err(|_| AppError::BadRequest("Failed to accept invite.".into()))?;
+    let inviter_uuid = Uuid::parse_str(&inviter_id).map_err(|_| AppError::BadRequest("Invalid invite.".into()))?;
+
+    let new_invite = NewInvite {
+        inviter_id: inviter_uuid,
+        invitee_id: Some(invitee_id),
+    };
+
+    create_invite(conn, &new_invite).await.map_err(|_| AppError::BadRequest("Failed to accept invite.".into()))?;
+
+    if let Err(err) = do_mission(conn, cache_conn, &inviter_id, "INVITE_FRIEND", None, &[]).await {
+        eprintln!("Failed to reward invite: {}", err);
+    }
+
+    if let Err(err) = evaluate_achievements(conn, inviter_uuid, AchievementEvent::FriendInvited).await {
+        eprintln!("Failed to evaluate achievements: {}", err);
+    }
 
     let _: () = cache_conn.del(&key).await.map_err(|e| AppError::BadRequest(format!("Redis error: {}", e)))?;
 
     Ok(())
 }
 
-async fn create_user_with_defaults(conn: &mut DbConn, email: &str, hashed_password: &str) -> Result<User, AppError> {
+async fn create_user_with_defaults(conn: &mut DbConn, email: &str, hashed_password: &str, timezone: Option<String>) -> Result<User, AppError> {
     let payload = NewUser {
         email: email.to_string(),
         password: hashed_password.to_string(),
         avatar_url: None,
         cover_url: None,
+        timezone,
     };
 
     let new_user = create_user(conn, &payload).await.map_err(|_| AppError::BadRequest("Failed to create new user.".into()))?;
@@ -82,6 +106,7 @@ pub async fn sign_up(Extension(pool): Extension<DbPool>, Extension(cache_pool):
     let email = payload.email;
     let password = payload.password;
     let invite_code = payload.code;
+    let timezone = payload.timezone;
 
     let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
 
@@ -91,12 +116,12 @@ pub async fn sign_up(Extension(pool): Extension<DbPool>, Extension(cache_pool):
 
     let hashed_password = hash_password(password).map_err(AppError::BadRequest)?;
 
-    let new_user = create_user_with_defaults(&mut conn, &email, &hashed_password).await?;
+    let new_user = create_user_with_defaults(&mut conn, &email, &hashed_password, timezone).await?;
 
     let mut cache_conn = get_cache_conn(&cache_pool).await.map_err(AppError::BadRequest)?;
 
     if let Some(code) = invite_code {
-        if let Err(err) = response_invite(&mut conn, &mut cache_conn, &code).await {
+        if let Err(err) = response_invite(&mut conn, &mut cache_conn, &code, new_user.id).await {
             eprintln!("{:?}", err);
         }
     }
@@ -146,7 +171,9 @@ pub async fn apple_sign_in(Extension(pool): Extension<DbPool>, Json(payload): Js
             Err(NotFound) => {
                 let hashed_password = hash_password(Uuid::new_v4().to_string()).map_err(AppError::BadRequest)?;
 
-                create_user_with_defaults(&mut conn, &email, &hashed_password).await?
+                let timezone = payload.get("timezone").and_then(Value::as_str).filter(|name| parse_timezone(name).is_some()).map(String::from);
+
+                create_user_with_defaults(&mut conn, &email, &hashed_password, timezone).await?
             }
             Err(err) => {
                 return Err(AppError::BadRequest(err.to_string()));
@@ -172,6 +199,10 @@ pub async fn check_valid_user(Extension(pool): Extension<DbPool>, Valid(Query(qu
 
     let feature_usage = get_feature_usage_by_user(&mut conn, &user.id.to_string()).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
 
+    let levels = get_levels(&mut conn).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let streak = get_check_in_streak_by_user(&mut conn, user.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
     let return_user = ReturnUser {
         id: user.id.to_string(),
         email: user.email,
@@ -179,6 +210,8 @@ pub async fn check_valid_user(Extension(pool): Extension<DbPool>, Valid(Query(qu
             route_calculation_count: feature_usage.route_calculation_count,
         },
         level: user.level,
+        level_progress: level_progress(&levels, user.level.unwrap_or(0), user.exp.unwrap_or(0)),
+        streak: get_return_check_in_streak(streak.as_ref(), get_today_date(get_user_timezone(&user.timezone))),
         avatar_url: user.avatar_url,
         cover_url: user.cover_url,
     };
@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::{
+    models::{check_in_streak::ReturnCheckInStreak, level::LevelProgress},
+    utils::time::validate_timezone,
+};
+
 #[derive(Serialize)]
 pub struct ReturnFeatureUsage {
     pub route_calculation_count: i32,
@@ -12,6 +17,8 @@ pub struct ReturnUser {
     pub email: String,
     pub feature_usage: ReturnFeatureUsage,
     pub level: Option<i32>,
+    pub level_progress: LevelProgress,
+    pub streak: ReturnCheckInStreak,
     pub avatar_url: Option<String>,
     pub cover_url: Option<String>,
 }
@@ -34,6 +41,9 @@ pub struct SignUpPayload {
     pub password: String,
 
     pub code: Option<String>,
+
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
 }
 
 #[derive(Validate, Deserialize)]
@@ -0,0 +1,21 @@
+use axum::{Extension, Json};
+use serde_json::{Value, json};
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    services::category::{build_category_tree, get_categories, get_category_google_types},
+    utils::error_handling::AppError,
+};
+
+/// Lists the category taxonomy as a tree of root categories.
+pub async fn search_categories(Extension(pool): Extension<DbPool>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let categories = get_categories(&mut conn).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let mappings = get_category_google_types(&mut conn).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "categories": build_category_tree(&categories, &mappings, None)
+    })))
+}
@@ -0,0 +1,232 @@
+use axum::{Extension, Json, extract::Path};
+use axum_valid::Valid;
+use chrono::Utc;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    config::db::{DbConn, DbPool, get_conn},
+    handlers::collection::{AddCollectionItemPayload, CreateCollectionPayload, ReorderCollectionItemsPayload, UpdateCollectionItemPayload, UpdateCollectionPayload},
+    models::{
+        collection::{Collection, CollectionVisibility, NewCollection, ReturnCollection, ReturnCollectionItem, UpdateCollection},
+        user::User,
+    },
+    services::{
+        collection::{
+            add_collection_item, create_collection, delete_collection, delete_collection_item, get_collection_by_id, get_collection_items, get_collections_by_user, reorder_collection_items,
+            update_collection, update_collection_item_note,
+        },
+        place::get_place_by_id,
+    },
+    utils::error_handling::AppError,
+};
+
+fn parse_id(id: &str, message: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(id).map_err(|_| AppError::NotFound(message.into()))
+}
+
+/// Loads the collection `collection_id` if `user_id` may read it. Private collections of other
+/// users are reported as not found.
+pub async fn get_readable_collection(conn: &mut DbConn, collection_id: Uuid, user_id: Uuid) -> Result<Collection, AppError> {
+    let collection = get_collection_by_id(conn, collection_id).await.map_err(|_| AppError::NotFound("Collection not found.".into()))?;
+
+    if !collection.is_readable_by(user_id) {
+        return Err(AppError::NotFound("Collection not found.".into()));
+    }
+
+    Ok(collection)
+}
+
+async fn get_owned_collection(conn: &mut DbConn, collection_id: &str, user_id: Uuid) -> Result<Collection, AppError> {
+    let collection = get_readable_collection(conn, parse_id(collection_id, "Collection not found.")?, user_id).await?;
+
+    if collection.user_id != user_id {
+        return Err(AppError::Forbidden("Only the owner can edit this collection.".into()));
+    }
+
+    Ok(collection)
+}
+
+pub async fn get_my_collections(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collections = get_collections_by_user(&mut conn, current_user.id, false).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "collections": collections
+    })))
+}
+
+/// Lists the public collections of a user, or every collection when the user is the caller.
+pub async fn get_user_collections(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path(user_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let user_id = parse_id(&user_id, "User not found.")?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collections = get_collections_by_user(&mut conn, user_id, user_id != current_user.id)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "collections": collections
+    })))
+}
+
+pub async fn create_new_collection(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Valid(Json(payload)): Valid<Json<CreateCollectionPayload>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let new_collection = NewCollection {
+        user_id: current_user.id,
+        name: payload.name,
+        description: payload.description,
+        visibility: payload.visibility.unwrap_or(CollectionVisibility::Private.as_str().to_string()),
+    };
+
+    let collection = create_collection(&mut conn, &new_collection)
+        .await
+        .map_err(|err| AppError::BadRequest(format!("Failed to create new collection. {}", err)))?;
+
+    Ok(Json(json!({
+        "collection": collection
+    })))
+}
+
+pub async fn get_collection(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path(collection_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let collection_id = parse_id(&collection_id, "Collection not found.")?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_readable_collection(&mut conn, collection_id, current_user.id).await?;
+
+    let items = get_collection_items(&mut conn, collection.id)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?
+        .into_iter()
+        .map(|(item, place)| ReturnCollectionItem { item, place })
+        .collect();
+
+    Ok(Json(json!({
+        "collection": ReturnCollection { collection, items }
+    })))
+}
+
+pub async fn update_existing_collection(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path(collection_id): Path<String>,
+    Valid(Json(payload)): Valid<Json<UpdateCollectionPayload>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_owned_collection(&mut conn, &collection_id, current_user.id).await?;
+
+    let changeset = UpdateCollection {
+        name: payload.name,
+        description: payload.description,
+        visibility: payload.visibility,
+        updated_at: Utc::now().naive_utc(),
+    };
+
+    let collection = update_collection(&mut conn, collection.id, &changeset)
+        .await
+        .map_err(|err| AppError::BadRequest(format!("Failed to update collection. {}", err)))?;
+
+    Ok(Json(json!({
+        "collection": collection
+    })))
+}
+
+pub async fn remove_collection(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path(collection_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_owned_collection(&mut conn, &collection_id, current_user.id).await?;
+
+    delete_collection(&mut conn, collection.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({})))
+}
+
+pub async fn add_item(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path(collection_id): Path<String>,
+    Valid(Json(payload)): Valid<Json<AddCollectionItemPayload>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_owned_collection(&mut conn, &collection_id, current_user.id).await?;
+
+    get_place_by_id(&mut conn, payload.place_id).await.map_err(|_| AppError::NotFound("Place not found.".into()))?;
+
+    let item = match add_collection_item(&mut conn, collection.id, payload.place_id, payload.note).await {
+        Ok(item) => item,
+        Err(DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => return Err(AppError::BadRequest("Place is already in the collection.".into())),
+        Err(err) => return Err(AppError::BadRequest(format!("Failed to add place to collection. {}", err))),
+    };
+
+    Ok(Json(json!({
+        "item": item
+    })))
+}
+
+pub async fn update_item(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path((collection_id, item_id)): Path<(String, String)>,
+    Valid(Json(payload)): Valid<Json<UpdateCollectionItemPayload>>,
+) -> Result<Json<Value>, AppError> {
+    let item_id = parse_id(&item_id, "Item not found.")?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_owned_collection(&mut conn, &collection_id, current_user.id).await?;
+
+    let item = update_collection_item_note(&mut conn, collection.id, item_id, payload.note)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?
+        .ok_or(AppError::NotFound("Item not found.".into()))?;
+
+    Ok(Json(json!({
+        "item": item
+    })))
+}
+
+pub async fn remove_item(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path((collection_id, item_id)): Path<(String, String)>) -> Result<Json<Value>, AppError> {
+    let item_id = parse_id(&item_id, "Item not found.")?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_owned_collection(&mut conn, &collection_id, current_user.id).await?;
+
+    let deleted = delete_collection_item(&mut conn, collection.id, item_id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    if deleted == 0 {
+        return Err(AppError::NotFound("Item not found.".into()));
+    }
+
+    Ok(Json(json!({})))
+}
+
+pub async fn reorder_items(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path(collection_id): Path<String>,
+    Json(payload): Json<ReorderCollectionItemsPayload>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let collection = get_owned_collection(&mut conn, &collection_id, current_user.id).await?;
+
+    match reorder_collection_items(&mut conn, collection.id, &payload.item_ids).await {
+        Ok(()) => {}
+        Err(diesel::result::Error::RollbackTransaction) => return Err(AppError::BadRequest("Items must list every item of the collection exactly once.".into())),
+        Err(err) => return Err(AppError::BadRequest(err.to_string())),
+    }
+
+    get_collection(Extension(pool), Extension(current_user), Path(collection.id.to_string())).await
+}
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::collection::CollectionVisibility;
+
+fn validate_visibility(visibility: &str) -> Result<(), ValidationError> {
+    if CollectionVisibility::parse(visibility).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("visibility").with_message("Visibility must be one of PUBLIC, PRIVATE or UNLISTED.".into()))
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateCollectionPayload {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters."))]
+    pub name: String,
+
+    #[validate(length(max = 1000, message = "Description must be at most 1000 characters."))]
+    pub description: Option<String>,
+
+    #[validate(custom(function = "validate_visibility"))]
+    pub visibility: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateCollectionPayload {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters."))]
+    pub name: Option<String>,
+
+    #[validate(length(max = 1000, message = "Description must be at most 1000 characters."))]
+    pub description: Option<String>,
+
+    #[validate(custom(function = "validate_visibility"))]
+    pub visibility: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct AddCollectionItemPayload {
+    pub place_id: Uuid,
+
+    #[validate(length(max = 500, message = "Note must be at most 500 characters."))]
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateCollectionItemPayload {
+    #[validate(length(max = 500, message = "Note must be at most 500 characters."))]
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ReorderCollectionItemsPayload {
+    pub item_ids: Vec<Uuid>,
+}
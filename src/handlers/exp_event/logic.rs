@@ -0,0 +1,70 @@
+use axum::{Extension, Json, extract::Path};
+use axum_valid::Valid;
+use chrono::Utc;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    handlers::exp_event::CreateExpEventPayload,
+    models::exp_event::NewExpEvent,
+    services::{
+        exp_event::{create_exp_event, delete_exp_event, get_exp_events},
+        mission::get_mission_by_code,
+    },
+    utils::error_handling::AppError,
+};
+
+/// Lists the running and upcoming EXP events.
+pub async fn search_exp_events(Extension(pool): Extension<DbPool>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let events = get_exp_events(&mut conn, Utc::now().naive_utc(), true).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "events": events
+    })))
+}
+
+pub async fn create_new_exp_event(Extension(pool): Extension<DbPool>, Valid(Json(payload)): Valid<Json<CreateExpEventPayload>>) -> Result<Json<Value>, AppError> {
+    if payload.starts_at >= payload.ends_at {
+        return Err(AppError::BadRequest("Event must start before it ends.".into()));
+    }
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    if let Some(mission_code) = &payload.mission_code {
+        get_mission_by_code(&mut conn, mission_code).await.map_err(|_| AppError::BadRequest("Mission not found.".into()))?;
+    }
+
+    let new_event = NewExpEvent {
+        name: payload.name,
+        multiplier: payload.multiplier,
+        mission_code: payload.mission_code,
+        place_type: payload.place_type,
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+    };
+
+    let event = create_exp_event(&mut conn, &new_event)
+        .await
+        .map_err(|err| AppError::BadRequest(format!("Failed to create new event. {}", err)))?;
+
+    Ok(Json(json!({
+        "event": event
+    })))
+}
+
+pub async fn remove_exp_event(Extension(pool): Extension<DbPool>, Path(event_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let event_id = Uuid::parse_str(&event_id).map_err(|_| AppError::NotFound("Event not found.".into()))?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let deleted = delete_exp_event(&mut conn, event_id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    if deleted == 0 {
+        return Err(AppError::NotFound("Event not found.".into()));
+    }
+
+    Ok(Json(json!({})))
+}
@@ -0,0 +1,5 @@
+mod logic;
+mod types;
+
+pub use logic::*;
+pub use types::*;
@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct CreateExpEventPayload {
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters."))]
+    pub name: String,
+
+    #[validate(range(min = 1.0, max = 10.0, message = "Multiplier must be between 1 and 10."))]
+    pub multiplier: f64,
+
+    pub mission_code: Option<String>,
+
+    pub place_type: Option<String>,
+
+    pub starts_at: NaiveDateTime,
+
+    pub ends_at: NaiveDateTime,
+}
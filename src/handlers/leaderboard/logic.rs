@@ -0,0 +1,77 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+};
+use axum_valid::Valid;
+use serde_json::{Value, json};
+
+use crate::{
+    config::{
+        cache::{CachePool, get_cache_conn},
+        db::{DbPool, get_conn},
+    },
+    handlers::leaderboard::{LeaderboardQuery, LeaderboardScope},
+    models::{leaderboard::LeaderboardPeriod, user::User},
+    services::{
+        follow::get_followee_ids,
+        leaderboard::{ensure_leaderboard, get_leaderboard_among, get_leaderboard_range, get_leaderboard_rank, to_leaderboard_entries},
+    },
+    utils::error_handling::AppError,
+};
+
+/// Number of users shown above and below the caller.
+const NEIGHBOUR_RADIUS: usize = 2;
+
+pub async fn get_leaderboard(
+    Extension(pool): Extension<DbPool>,
+    Extension(cache_pool): Extension<CachePool>,
+    Extension(current_user): Extension<User>,
+    Path(period): Path<LeaderboardPeriod>,
+    Valid(Query(query)): Valid<Query<LeaderboardQuery>>,
+) -> Result<Json<Value>, AppError> {
+    let limit = query.limit.unwrap_or(10);
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let mut cache_conn = get_cache_conn(&cache_pool).await.map_err(AppError::BadRequest)?;
+
+    let key = ensure_leaderboard(&mut conn, &mut cache_conn, period).await.map_err(AppError::BadRequest)?;
+
+    let (top, rank, neighbours) = if query.scope == Some(LeaderboardScope::Following) {
+        let mut user_ids = get_followee_ids(&mut conn, current_user.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+        user_ids.push(current_user.id);
+
+        let rows = get_leaderboard_among(&mut cache_conn, &key, &user_ids).await.map_err(AppError::BadRequest)?;
+
+        let position = rows.iter().position(|(_, user_id, _)| *user_id == current_user.id).unwrap_or_default();
+
+        let neighbours = rows[position.saturating_sub(NEIGHBOUR_RADIUS)..(position + NEIGHBOUR_RADIUS + 1).min(rows.len())].to_vec();
+        let top = rows.into_iter().take(limit).collect();
+
+        (top, Some(position), neighbours)
+    } else {
+        let top = get_leaderboard_range(&mut cache_conn, &key, 0, limit as isize - 1).await.map_err(AppError::BadRequest)?;
+
+        let rank = get_leaderboard_rank(&mut cache_conn, &key, current_user.id).await.map_err(AppError::BadRequest)?;
+
+        let neighbours = match rank {
+            Some(rank) => get_leaderboard_range(&mut cache_conn, &key, rank.saturating_sub(NEIGHBOUR_RADIUS) as isize, (rank + NEIGHBOUR_RADIUS) as isize)
+                .await
+                .map_err(AppError::BadRequest)?,
+            None => vec![],
+        };
+
+        (top, rank, neighbours)
+    };
+
+    let top = to_leaderboard_entries(&mut conn, top).await.map_err(AppError::BadRequest)?;
+    let neighbours = to_leaderboard_entries(&mut conn, neighbours).await.map_err(AppError::BadRequest)?;
+
+    Ok(Json(json!({
+        "leaderboard": top,
+        "me": {
+            "rank": rank.map(|rank| rank + 1),
+            "neighbours": neighbours
+        }
+    })))
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardScope {
+    All,
+    Following,
+}
+
+#[derive(Validate, Deserialize)]
+pub struct LeaderboardQuery {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100."))]
+    pub limit: Option<usize>,
+
+    pub scope: Option<LeaderboardScope>,
+}
@@ -1,27 +1,107 @@
-use axum::{Extension, Json};
+use axum::{Extension, Json, extract::Path};
+use axum_valid::Valid;
+use chrono::{NaiveDateTime, Utc};
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
 use serde_json::{Value, json};
+use uuid::Uuid;
 
 use crate::{
-    config::db::{DbPool, get_conn},
-    models::mission::NewMission,
-    services::mission::{create_mission, get_missions},
-    utils::error_handling::AppError,
+    config::{
+        cache::{CachePool, get_cache_conn},
+        db::{DbPool, get_conn},
+    },
+    handlers::mission::{CreateMissionPayload, ScheduleMissionPayload, UpdateMissionPayload},
+    models::{
+        gift_reward::GiftReward,
+        mission::{MissionScheduleChangeset, NewMission, UpdateMission},
+        user::User,
+    },
+    services::{
+        exp_history::has_exp_history_by_source,
+        mission::{create_mission, delete_mission, get_available_missions, get_mission_by_id, get_missions, get_missions_progress, set_mission_disabled, update_mission, update_mission_schedule},
+    },
+    utils::{error_handling::AppError, time::get_user_timezone},
 };
 
-pub async fn search_missions(Extension(pool): Extension<DbPool>) -> Result<Json<Value>, AppError> {
+fn validate_gift_reward(reward_type: Option<&str>, count: Option<i32>, code: Option<&str>) -> Result<(), AppError> {
+    if reward_type.is_some() && GiftReward::parse(reward_type, count, code).is_none() {
+        return Err(AppError::BadRequest(
+            "Gift reward must be ROUTE_CALCULATION or PREMIUM_DAYS with a positive count, or BADGE with an achievement code.".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_schedule(active_from: Option<NaiveDateTime>, active_until: Option<NaiveDateTime>) -> Result<(), AppError> {
+    if let (Some(from), Some(until)) = (active_from, active_until)
+        && from >= until
+    {
+        return Err(AppError::BadRequest("Mission must start before it ends.".into()));
+    }
+
+    Ok(())
+}
+
+fn parse_mission_id(mission_id: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(mission_id).map_err(|_| AppError::NotFound("Mission not found.".into()))
+}
+
+/// Lists the mission catalogue. Admins see every mission; other users only the enabled missions
+/// inside their active window.
+pub async fn search_missions(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
     let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
 
-    let missions = get_missions(&mut conn).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+    let missions = if current_user.is_admin {
+        get_missions(&mut conn).await
+    } else {
+        get_available_missions(&mut conn, Utc::now().naive_utc()).await
+    }
+    .map_err(|err| AppError::BadRequest(err.to_string()))?;
 
     Ok(Json(json!({
         "missions": missions
     })))
 }
 
-pub async fn create_new_mission(Extension(pool): Extension<DbPool>, Json(payload): Json<NewMission>) -> Result<Json<Value>, AppError> {
+pub async fn get_my_missions(Extension(pool): Extension<DbPool>, Extension(cache_pool): Extension<CachePool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
     let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
 
-    let mission = create_mission(&mut conn, &payload)
+    let mut cache_conn = get_cache_conn(&cache_pool).await.map_err(AppError::BadRequest)?;
+
+    let missions = get_available_missions(&mut conn, Utc::now().naive_utc()).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let missions = get_missions_progress(&mut conn, &mut cache_conn, &current_user.id.to_string(), get_user_timezone(&current_user.timezone), missions)
+        .await
+        .map_err(AppError::BadRequest)?;
+
+    Ok(Json(json!({
+        "missions": missions
+    })))
+}
+
+pub async fn create_new_mission(Extension(pool): Extension<DbPool>, Valid(Json(payload)): Valid<Json<CreateMissionPayload>>) -> Result<Json<Value>, AppError> {
+    validate_gift_reward(payload.gift_reward_type.as_deref(), payload.gift_reward_count, payload.gift_reward_code.as_deref())?;
+
+    validate_schedule(payload.active_from, payload.active_until)?;
+
+    let new_mission = NewMission {
+        code: payload.code,
+        name: payload.name,
+        description: payload.description,
+        exp_reward: payload.exp_reward,
+        gift_reward_count: payload.gift_reward_count,
+        gift_reward_type: payload.gift_reward_type,
+        gift_reward_code: payload.gift_reward_code,
+        max_per_day: payload.max_per_day,
+        period: payload.period,
+        active_from: payload.active_from,
+        active_until: payload.active_until,
+    };
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let mission = create_mission(&mut conn, &new_mission)
         .await
         .map_err(|err| AppError::BadRequest(format!("Failed to create new mission. {}", err)))?;
 
@@ -29,3 +109,107 @@ pub async fn create_new_mission(Extension(pool): Extension<DbPool>, Json(payload
         "mission": mission
     })))
 }
+
+pub async fn update_existing_mission(Extension(pool): Extension<DbPool>, Path(mission_id): Path<String>, Valid(Json(payload)): Valid<Json<UpdateMissionPayload>>) -> Result<Json<Value>, AppError> {
+    let mission_id = parse_mission_id(&mission_id)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let mission = get_mission_by_id(&mut conn, mission_id).await.map_err(|_| AppError::NotFound("Mission not found.".into()))?;
+
+    validate_gift_reward(
+        payload.gift_reward_type.as_ref().unwrap_or(&mission.gift_reward_type).as_deref(),
+        payload.gift_reward_count.unwrap_or(mission.gift_reward_count),
+        payload.gift_reward_code.as_ref().unwrap_or(&mission.gift_reward_code).as_deref(),
+    )?;
+
+    let changeset = UpdateMission {
+        name: payload.name,
+        description: payload.description,
+        exp_reward: payload.exp_reward,
+        gift_reward_count: payload.gift_reward_count,
+        gift_reward_type: payload.gift_reward_type,
+        gift_reward_code: payload.gift_reward_code,
+        max_per_day: payload.max_per_day,
+        period: payload.period,
+    };
+
+    let mission = update_mission(&mut conn, mission_id, &changeset)
+        .await
+        .map_err(|err| AppError::BadRequest(format!("Failed to update mission. {}", err)))?;
+
+    Ok(Json(json!({
+        "mission": mission
+    })))
+}
+
+pub async fn schedule_mission(Extension(pool): Extension<DbPool>, Path(mission_id): Path<String>, Json(payload): Json<ScheduleMissionPayload>) -> Result<Json<Value>, AppError> {
+    let mission_id = parse_mission_id(&mission_id)?;
+
+    validate_schedule(payload.active_from, payload.active_until)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let changeset = MissionScheduleChangeset {
+        active_from: payload.active_from,
+        active_until: payload.active_until,
+    };
+
+    let mission = update_mission_schedule(&mut conn, mission_id, &changeset)
+        .await
+        .map_err(|_| AppError::NotFound("Mission not found.".into()))?;
+
+    Ok(Json(json!({
+        "mission": mission
+    })))
+}
+
+pub async fn disable_mission(Extension(pool): Extension<DbPool>, Path(mission_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mission_id = parse_mission_id(&mission_id)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let mission = set_mission_disabled(&mut conn, mission_id, true).await.map_err(|_| AppError::NotFound("Mission not found.".into()))?;
+
+    Ok(Json(json!({
+        "mission": mission
+    })))
+}
+
+pub async fn enable_mission(Extension(pool): Extension<DbPool>, Path(mission_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mission_id = parse_mission_id(&mission_id)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let mission = set_mission_disabled(&mut conn, mission_id, false).await.map_err(|_| AppError::NotFound("Mission not found.".into()))?;
+
+    Ok(Json(json!({
+        "mission": mission
+    })))
+}
+
+/// Deletes a mission that was never completed. Completed missions keep their history and can only
+/// be disabled.
+pub async fn remove_mission(Extension(pool): Extension<DbPool>, Path(mission_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mission_id = parse_mission_id(&mission_id)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let mission = get_mission_by_id(&mut conn, mission_id).await.map_err(|_| AppError::NotFound("Mission not found.".into()))?;
+
+    if has_exp_history_by_source(&mut conn, &mission.code).await.map_err(|err| AppError::BadRequest(err.to_string()))? {
+        return Err(AppError::BadRequest("Mission has already been completed, disable it instead.".into()));
+    }
+
+    let deleted = match delete_mission(&mut conn, mission_id).await {
+        Ok(deleted) => deleted,
+        Err(DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => return Err(AppError::BadRequest("Mission is still rewarded by a quest.".into())),
+        Err(err) => return Err(AppError::BadRequest(err.to_string())),
+    };
+
+    if deleted == 0 {
+        return Err(AppError::NotFound("Mission not found.".into()));
+    }
+
+    Ok(Json(json!({})))
+}
@@ -0,0 +1,97 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Deserializer};
+use validator::{Validate, ValidationError};
+
+use crate::models::mission::MissionPeriod;
+
+fn validate_mission_code(code: &str) -> Result<(), ValidationError> {
+    let is_valid =
+        (3..=20).contains(&code.len()) && code.starts_with(|char: char| char.is_ascii_uppercase()) && code.chars().all(|char| char.is_ascii_uppercase() || char.is_ascii_digit() || char == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("code").with_message("Code must be 3 to 20 uppercase letters, digits or underscores, starting with a letter.".into()))
+    }
+}
+
+/// Deserializes a present field, `null` included, as `Some`, so that a missing field stays `None`.
+fn deserialize_present<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Option<Option<T>>, D::Error> {
+    Option::deserialize(deserializer).map(Some)
+}
+
+fn validate_mission_period(period: &str) -> Result<(), ValidationError> {
+    if MissionPeriod::parse(period).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("period").with_message("Period must be one of daily, weekly, monthly or lifetime.".into()))
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateMissionPayload {
+    #[validate(custom(function = "validate_mission_code"))]
+    pub code: String,
+
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters."))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    #[validate(range(min = 0, max = 10000, message = "EXP reward must be between 0 and 10000."))]
+    pub exp_reward: i32,
+
+    #[validate(range(min = 1, max = 1000, message = "Gift reward count must be between 1 and 1000."))]
+    pub gift_reward_count: Option<i32>,
+
+    pub gift_reward_type: Option<String>,
+
+    pub gift_reward_code: Option<String>,
+
+    #[validate(range(min = 1, max = 1000, message = "Max completions must be between 1 and 1000."))]
+    pub max_per_day: Option<i32>,
+
+    #[validate(custom(function = "validate_mission_period"))]
+    pub period: Option<String>,
+
+    pub active_from: Option<NaiveDateTime>,
+
+    pub active_until: Option<NaiveDateTime>,
+}
+
+/// Changes to a mission. Missing fields are left unchanged, and optional fields set to `null` are
+/// cleared. The code cannot be changed, since it keys the mission's history.
+#[derive(Deserialize, Validate)]
+pub struct UpdateMissionPayload {
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters."))]
+    pub name: Option<String>,
+
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub description: Option<Option<String>>,
+
+    #[validate(range(min = 0, max = 10000, message = "EXP reward must be between 0 and 10000."))]
+    pub exp_reward: Option<i32>,
+
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[validate(range(min = 1, max = 1000, message = "Gift reward count must be between 1 and 1000."))]
+    pub gift_reward_count: Option<Option<i32>>,
+
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub gift_reward_type: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub gift_reward_code: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[validate(range(min = 1, max = 1000, message = "Max completions must be between 1 and 1000."))]
+    pub max_per_day: Option<Option<i32>>,
+
+    #[validate(custom(function = "validate_mission_period"))]
+    pub period: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ScheduleMissionPayload {
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+}
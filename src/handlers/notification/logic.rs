@@ -0,0 +1,74 @@
+use axum::{
+    extract::{Extension, Query},
+    response::Html,
+};
+use axum_valid::Valid;
+use diesel::OptionalExtension;
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    handlers::notification::UnsubscribeQuery,
+    models::{email_unsubscribe::NewEmailUnsubscribe, notification_preference::NewNotificationPreference},
+    services::{
+        email_unsubscribe::create_email_unsubscribe,
+        notification_preference::{get_notification_preferences_by_user, upsert_notification_preference},
+        user::get_user_by_email,
+    },
+    utils::{
+        error_handling::AppError,
+        jwt::verify_unsubscribe_token,
+        mail_template::{unsubscribe_confirmation_page, unsubscribed_page},
+    },
+};
+
+/// Target of the unsubscribe links in outgoing emails. Only asks for confirmation, so that link
+/// scanners and previews opening the link do not unsubscribe anyone.
+pub async fn confirm_unsubscribe(Valid(Query(query)): Valid<Query<UnsubscribeQuery>>) -> Result<Html<String>, AppError> {
+    let claims = verify_unsubscribe_token(&query.token).map_err(AppError::BadRequest)?.claims;
+
+    let page = unsubscribe_confirmation_page(&claims.email, &claims.category, &query.token).map_err(AppError::BadRequest)?;
+
+    Ok(Html(page))
+}
+
+/// One-click unsubscribe (RFC 8058) target of the `List-Unsubscribe` headers in outgoing emails,
+/// also submitted by the confirmation page. Works for addresses that do not belong to a user yet,
+/// e.g. invited friends.
+pub async fn unsubscribe(Extension(pool): Extension<DbPool>, Valid(Query(query)): Valid<Query<UnsubscribeQuery>>) -> Result<Html<String>, AppError> {
+    let claims = verify_unsubscribe_token(&query.token).map_err(AppError::BadRequest)?.claims;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let new_unsubscribe = NewEmailUnsubscribe {
+        email: claims.email.clone(),
+        category: claims.category.clone(),
+    };
+
+    create_email_unsubscribe(&mut conn, &new_unsubscribe)
+        .await
+        .map_err(|_| AppError::BadRequest("Failed to unsubscribe.".into()))?;
+
+    let user = get_user_by_email(&mut conn, &claims.email).await.optional().map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    if let Some(user) = user {
+        let preferences = get_notification_preferences_by_user(&mut conn, user.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+        let current = preferences.iter().find(|preference| preference.category == claims.category);
+
+        let new_preference = NewNotificationPreference {
+            user_id: user.id,
+            category: claims.category.clone(),
+            in_app: current.is_none_or(|preference| preference.in_app),
+            push: current.is_none_or(|preference| preference.push),
+            email: false,
+        };
+
+        upsert_notification_preference(&mut conn, &new_preference)
+            .await
+            .map_err(|_| AppError::BadRequest("Failed to unsubscribe.".into()))?;
+    }
+
+    let page = unsubscribed_page(&claims.email, &claims.category).map_err(AppError::BadRequest)?;
+
+    Ok(Html(page))
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Validate, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
@@ -1,4 +1,10 @@
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+};
+use axum_valid::Valid;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use diesel::result::Error::NotFound;
 use redis::AsyncCommands;
 use serde_json::{Value, json};
@@ -10,63 +16,47 @@ use crate::{
         cache::{CacheConn, CachePool, get_cache_conn},
         db::{DbConn, DbPool, get_conn},
     },
-    handlers::place::UpsertPlacePayload,
+    handlers::place::{NearbyPlacesQuery, TrendingPlacesQuery, UpsertPlacePayload},
     models::{
-        place::{NewPlace, Place},
+        achievement::AchievementEvent,
+        opening_hours::OpeningHours,
+        place::{NewPlace, Place, PlaceChangeset, PlaceFilter, ReturnNearbyPlace},
+        quest::QuestAction,
         review::NewReview,
         user::User,
         user_place_access::NewUserPlaceAccess,
     },
     services::{
-        place::{create_place, get_place_by_place_id, increase_place_view},
-        review::create_review,
+        achievement::evaluate_achievements,
+        category::{get_matching_google_types, get_place_category},
+        place::{create_place, get_nearby_places, get_place_by_place_id, get_trending_places, increase_place_view, is_place_stale, refresh_place},
+        quest::evaluate_quests,
+        recommendation::get_recommended_places,
+        review::{create_review, get_review_stats},
         user_place_access::create_user_place_access,
     },
     utils::{
         error_handling::AppError,
-        time::{get_seconds_to_midnight, get_today},
+        pagination::PaginationQuery,
+        time::{get_seconds_to_midnight, get_today, get_today_date, get_user_timezone},
     },
 };
 
-const IGNORED_TYPES: &[&str] = &[
-    "establishment",
-    "point_of_interest",
-    "premise",
-    "geocode",
-    "political",
-    "colloquial_area",
-    "locality",
-    "sublocality",
-    "sublocality_level_1",
-    "sublocality_level_2",
-    "sublocality_level_3",
-    "sublocality_level_4",
-    "sublocality_level_5",
-    "administrative_area_level_1",
-    "administrative_area_level_2",
-    "administrative_area_level_3",
-    "administrative_area_level_4",
-    "administrative_area_level_5",
-    "country",
-    "postal_code",
-    "postal_code_prefix",
-    "postal_code_suffix",
-    "postal_town",
-    "neighborhood",
-    "route",
-    "intersection",
-    "street_address",
-    "street_number",
-    "floor",
-    "room",
-    "plus_code",
-    "natural_feature",
-    "airport",
-    "place_of_worship",
-];
-
-async fn create_user_place_access_today<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: Uuid, place_id: Uuid, types: Option<Vec<Option<String>>>) {
-    let today = get_today();
+/// Google types matching the `type` query parameter, a category code or raw Google type.
+async fn get_place_types_filter(conn: &mut DbConn, place_type: Option<&str>) -> Result<Option<Vec<String>>, AppError> {
+    let Some(place_type) = place_type else {
+        return Ok(None);
+    };
+
+    let place_types = get_matching_google_types(conn, place_type).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Some(place_types))
+}
+
+/// Records the first visit of the place `place_id` by the user `user_id` on their current day,
+/// then evaluates their achievements and quests.
+async fn create_user_place_access_today<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: Uuid, tz: Tz, place_id: Uuid, types: Option<Vec<Option<String>>>) {
+    let today = get_today_date(tz);
 
     let cache_key = format!("access:{}:{}:{}", user_id, today, place_id);
 
@@ -78,24 +68,51 @@ async fn create_user_place_access_today<'a>(conn: &mut DbConn, cache_conn: &mut
         }
     };
 
-    if current_access.is_none() {
-        let type_ = types
-            .as_ref()
-            .and_then(|vec| vec.iter().flatten().find(|s| !IGNORED_TYPES.contains(&s.as_str())))
-            .cloned()
-            .unwrap_or_default();
+    if current_access.is_some() {
+        return;
+    }
 
-        let new_user_place_access = NewUserPlaceAccess { user_id, place_id, type_ };
+    let types: Vec<String> = types.unwrap_or_default().into_iter().flatten().collect();
 
-        if (create_user_place_access(conn, &new_user_place_access).await).is_err() {
-            eprintln!("Failed to create user place access.");
+    let type_ = match get_place_category(conn, &types).await {
+        Ok(category) => category.unwrap_or_default(),
+        Err(err) => {
+            eprintln!("Failed to resolve place category: {}", err);
+            String::new()
         }
-    } else {
-        let expire_time = get_seconds_to_midnight();
+    };
+
+    let new_user_place_access = NewUserPlaceAccess {
+        user_id,
+        place_id,
+        type_,
+        visited_on: today,
+    };
 
-        if let Err(err) = cache_conn.set_ex::<&str, bool, u64>(&cache_key, true, expire_time as u64).await {
-            eprintln!("Failed to cache access: {}", err);
+    let is_new_visit = match create_user_place_access(conn, &new_user_place_access).await {
+        Ok(access) => access.is_some(),
+        Err(err) => {
+            eprintln!("Failed to create user place access: {}", err);
+            return;
         }
+    };
+
+    let expire_time = get_seconds_to_midnight(tz);
+
+    if let Err(err) = cache_conn.set_ex::<&str, bool, u64>(&cache_key, true, expire_time as u64).await {
+        eprintln!("Failed to cache access: {}", err);
+    }
+
+    if !is_new_visit {
+        return;
+    }
+
+    if let Err(err) = evaluate_achievements(conn, user_id, AchievementEvent::PlaceAccessed).await {
+        eprintln!("Failed to evaluate achievements: {}", err);
+    }
+
+    if let Err(err) = evaluate_quests(conn, cache_conn, user_id, tz, QuestAction::Visit).await {
+        eprintln!("Failed to evaluate quests: {}", err);
     }
 }
 
@@ -128,8 +145,15 @@ pub async fn upsert_place(
     Extension(pool): Extension<DbPool>,
     Extension(cache_pool): Extension<CachePool>,
     Extension(current_user): Extension<User>,
-    Json(payload): Json<UpsertPlacePayload>,
+    Json(mut payload): Json<UpsertPlacePayload>,
 ) -> Result<Json<Value>, AppError> {
+    payload.place.opening_hours = payload
+        .place
+        .opening_hours
+        .as_ref()
+        .and_then(OpeningHours::from_google)
+        .and_then(|opening_hours| serde_json::to_value(opening_hours).ok());
+
     let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
 
     let place = &payload.place;
@@ -137,7 +161,19 @@ pub async fn upsert_place(
 
     let place_id = &place.place_id;
 
+    let now = Utc::now().naive_utc();
+
+    // Future times are clamped to now so a skewed client clock cannot defer the next refresh.
+    let fetched_at = payload.fetched_at.and_then(DateTime::from_timestamp_millis).map_or(now, |fetched_at| fetched_at.naive_utc().min(now));
+
     let existing_place = match get_place_by_place_id(&mut conn, place_id).await {
+        Ok(existing_place) if is_place_stale(existing_place.updated_at, fetched_at) => {
+            let changeset = PlaceChangeset::from_new_place(place, fetched_at);
+
+            refresh_place(&mut conn, existing_place.id, changeset, reviews)
+                .await
+                .map_err(|_| AppError::BadRequest("Failed to refresh place.".into()))?
+        }
         Ok(place) => place,
         Err(NotFound) => create_place_with_defaults(&mut conn, place, reviews).await?,
         Err(err) => {
@@ -148,6 +184,7 @@ pub async fn upsert_place(
     let place_types_clone = existing_place.types.clone();
     let place_id = existing_place.id;
     let user_id = current_user.id;
+    let tz = get_user_timezone(&current_user.timezone);
     let cache_pool_clone = cache_pool.clone();
     let pool_clone = pool.clone();
 
@@ -168,7 +205,7 @@ pub async fn upsert_place(
             }
         };
 
-        create_user_place_access_today(&mut conn, &mut cache_conn, user_id, place_id, place_types_clone).await;
+        create_user_place_access_today(&mut conn, &mut cache_conn, user_id, tz, place_id, place_types_clone).await;
     });
 
     Ok(Json(json!({
@@ -186,7 +223,7 @@ pub async fn increase_view(
 
     let mut cache_conn = get_cache_conn(&cache_pool).await.map_err(AppError::BadRequest)?;
 
-    let today = get_today();
+    let today = get_today(get_user_timezone(&current_user.timezone));
 
     let place = {
         let cache_key = format!("view:{}:{}:{}", &current_user.id.to_string(), today, &place_id);
@@ -211,6 +248,7 @@ pub async fn increase_view(
     };
 
     let user_id = current_user.id;
+    let tz = get_user_timezone(&current_user.timezone);
     let place_id = place.id;
     let place_types_clone = place.types.clone();
     let cache_pool_clone = cache_pool.clone();
@@ -233,10 +271,119 @@ pub async fn increase_view(
             }
         };
 
-        create_user_place_access_today(&mut conn, &mut cache_conn, user_id, place_id, place_types_clone).await;
+        create_user_place_access_today(&mut conn, &mut cache_conn, user_id, tz, place_id, place_types_clone).await;
     });
 
     Ok(Json(json!({
         "place": place
     })))
 }
+
+pub async fn search_nearby_places(
+    Extension(pool): Extension<DbPool>,
+    Valid(Query(query)): Valid<Query<NearbyPlacesQuery>>,
+    Valid(Query(pagination)): Valid<Query<PaginationQuery>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let place_types = get_place_types_filter(&mut conn, query.place_type.as_deref()).await?;
+
+    let filter = PlaceFilter {
+        place_types: place_types.as_deref(),
+        open_at: query.open_at(),
+    };
+
+    let (places, total) = get_nearby_places(&mut conn, query.lat, query.lng, query.radius(), filter, pagination.limit(), pagination.offset())
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let places: Vec<ReturnNearbyPlace> = places.into_iter().map(|(place, distance)| ReturnNearbyPlace { place, distance }).collect();
+
+    Ok(Json(json!({
+        "places": places,
+        "page": pagination.page(),
+        "limit": pagination.limit(),
+        "total": total
+    })))
+}
+
+/// Recommends unvisited places near the user, best first.
+///
+/// Only the `CANDIDATE_LIMIT` nearest unvisited places are ranked, so the response tells whether
+/// another page exists instead of a total count.
+pub async fn search_recommended_places(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Valid(Query(query)): Valid<Query<NearbyPlacesQuery>>,
+    Valid(Query(pagination)): Valid<Query<PaginationQuery>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let place_types = get_place_types_filter(&mut conn, query.place_type.as_deref()).await?;
+
+    let filter = PlaceFilter {
+        place_types: place_types.as_deref(),
+        open_at: query.open_at(),
+    };
+
+    let recommendations = get_recommended_places(&mut conn, current_user.id, query.lat, query.lng, query.radius(), filter)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let has_more = recommendations.len() > (pagination.offset() + pagination.limit()) as usize;
+
+    let places: Vec<_> = recommendations.into_iter().skip(pagination.offset() as usize).take(pagination.limit() as usize).collect();
+
+    Ok(Json(json!({
+        "places": places,
+        "page": pagination.page(),
+        "limit": pagination.limit(),
+        "has_more": has_more
+    })))
+}
+
+pub async fn search_trending_places(
+    Extension(pool): Extension<DbPool>,
+    Valid(Query(query)): Valid<Query<TrendingPlacesQuery>>,
+    Valid(Query(pagination)): Valid<Query<PaginationQuery>>,
+) -> Result<Json<Value>, AppError> {
+    let region = query.region().map_err(|err| AppError::BadRequest(err.into()))?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let place_types = get_place_types_filter(&mut conn, query.place_type.as_deref()).await?;
+
+    let filter = PlaceFilter {
+        place_types: place_types.as_deref(),
+        open_at: query.open_at(),
+    };
+
+    let (places, total) = get_trending_places(&mut conn, region, filter, pagination.limit(), pagination.offset())
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "places": places,
+        "page": pagination.page(),
+        "limit": pagination.limit(),
+        "total": total
+    })))
+}
+
+pub async fn get_place_detail(Extension(pool): Extension<DbPool>, Path(place_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let place = match get_place_by_place_id(&mut conn, &place_id).await {
+        Ok(place) => place,
+        Err(NotFound) => return Err(AppError::NotFound("Place not found.".into())),
+        Err(err) => return Err(AppError::BadRequest(err.to_string())),
+    };
+
+    let review_stats = get_review_stats(&mut conn, place.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "open_now": place.is_open_at(Utc::now().naive_utc()),
+        "place": place,
+        "review_stats": review_stats
+    })))
+}
@@ -1,4 +1,6 @@
+use chrono::{NaiveDateTime, Utc};
 use serde::Deserialize;
+use validator::Validate;
 
 use crate::models::{place::NewPlace, review::NewReview};
 
@@ -6,4 +8,77 @@ use crate::models::{place::NewPlace, review::NewReview};
 pub struct UpsertPlacePayload {
     pub place: NewPlace,
     pub reviews: Vec<NewReview>,
+    /// Time the client fetched the place from Google, in milliseconds since the epoch. Defaults to
+    /// now.
+    pub fetched_at: Option<i64>,
+}
+
+/// Time `open_now` filters on, if requested.
+fn get_open_at(open_now: Option<bool>) -> Option<NaiveDateTime> {
+    open_now.unwrap_or(false).then(|| Utc::now().naive_utc())
+}
+
+#[derive(Validate, Deserialize)]
+pub struct NearbyPlacesQuery {
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90."))]
+    pub lat: f64,
+
+    #[validate(range(min = -180.0, max = 180.0, message = "Longitude must be between -180 and 180."))]
+    pub lng: f64,
+
+    /// Search radius in meters.
+    #[validate(range(min = 1.0, max = 50000.0, message = "Radius must be between 1 and 50000 meters."))]
+    pub radius: Option<f64>,
+
+    /// Category code, or raw Google type.
+    #[serde(rename = "type")]
+    pub place_type: Option<String>,
+
+    /// Only places known to be open now.
+    pub open_now: Option<bool>,
+}
+
+#[derive(Validate, Deserialize)]
+pub struct TrendingPlacesQuery {
+    /// Center of the region, required together with `lng`.
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90."))]
+    pub lat: Option<f64>,
+
+    #[validate(range(min = -180.0, max = 180.0, message = "Longitude must be between -180 and 180."))]
+    pub lng: Option<f64>,
+
+    /// Radius of the region in meters.
+    #[validate(range(min = 1.0, max = 50000.0, message = "Radius must be between 1 and 50000 meters."))]
+    pub radius: Option<f64>,
+
+    /// Category code, or raw Google type.
+    #[serde(rename = "type")]
+    pub place_type: Option<String>,
+
+    /// Only places known to be open now.
+    pub open_now: Option<bool>,
+}
+
+impl TrendingPlacesQuery {
+    pub fn open_at(&self) -> Option<NaiveDateTime> {
+        get_open_at(self.open_now)
+    }
+
+    pub fn region(&self) -> Result<Option<(f64, f64, f64)>, &'static str> {
+        match (self.lat, self.lng) {
+            (Some(lat), Some(lng)) => Ok(Some((lat, lng, self.radius.unwrap_or(5000.0)))),
+            (None, None) => Ok(None),
+            _ => Err("Latitude and longitude must be provided together."),
+        }
+    }
+}
+
+impl NearbyPlacesQuery {
+    pub fn open_at(&self) -> Option<NaiveDateTime> {
+        get_open_at(self.open_now)
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius.unwrap_or(1000.0)
+    }
 }
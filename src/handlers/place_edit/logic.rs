@@ -0,0 +1,142 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+};
+use axum_valid::Valid;
+use diesel::result::{
+    DatabaseErrorKind,
+    Error::{DatabaseError, NotFound, RollbackTransaction},
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    handlers::place_edit::{PlaceEditQuery, ReviewPlaceEditPayload, SuggestPlaceEditPayload},
+    models::{
+        place_edit::{NewPlaceEditSuggestion, PlaceEditStatus, ReturnPlaceEditSuggestion, ReturnPlaceEditSuggestionDetail},
+        user::User,
+    },
+    services::{
+        place::get_place_by_place_id,
+        place_edit::{create_place_edit_suggestions, get_place_edit_events, get_place_edit_suggestion_by_id, get_place_edit_suggestions, review_place_edit_suggestion},
+    },
+    utils::{error_handling::AppError, pagination::PaginationQuery},
+};
+
+fn parse_id(id: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(id).map_err(|_| AppError::NotFound("Suggestion not found.".into()))
+}
+
+pub async fn suggest_place_edit(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path(place_id): Path<String>,
+    Valid(Json(payload)): Valid<Json<SuggestPlaceEditPayload>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let place = match get_place_by_place_id(&mut conn, &place_id).await {
+        Ok(place) => place,
+        Err(NotFound) => return Err(AppError::NotFound("Place not found.".into())),
+        Err(err) => return Err(AppError::BadRequest(err.to_string())),
+    };
+
+    let new_suggestions: Vec<NewPlaceEditSuggestion> = payload
+        .changes
+        .into_iter()
+        .map(|change| NewPlaceEditSuggestion {
+            place_id: place.id,
+            user_id: current_user.id,
+            field: change.field,
+            proposed_value: change.value.map(|value| value.trim().to_string()),
+            reason: payload.reason.clone(),
+        })
+        .collect();
+
+    let suggestions = match create_place_edit_suggestions(&mut conn, &place, &new_suggestions).await {
+        Ok(suggestions) => suggestions,
+        Err(DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => return Err(AppError::BadRequest("You already have a pending suggestion for this field.".into())),
+        Err(err) => return Err(AppError::BadRequest(format!("Failed to create suggestion. {}", err))),
+    };
+
+    Ok(Json(json!({
+        "suggestions": suggestions
+    })))
+}
+
+/// Lists suggestions with the requested status, pending by default, oldest first.
+pub async fn search_place_edit_suggestions(
+    Extension(pool): Extension<DbPool>,
+    Valid(Query(query)): Valid<Query<PlaceEditQuery>>,
+    Valid(Query(pagination)): Valid<Query<PaginationQuery>>,
+) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let (suggestions, total) = get_place_edit_suggestions(&mut conn, query.status(), pagination.limit(), pagination.offset())
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let suggestions: Vec<ReturnPlaceEditSuggestion> = suggestions.into_iter().map(|(suggestion, place)| ReturnPlaceEditSuggestion { suggestion, place }).collect();
+
+    Ok(Json(json!({
+        "suggestions": suggestions,
+        "page": pagination.page(),
+        "limit": pagination.limit(),
+        "total": total
+    })))
+}
+
+pub async fn get_place_edit_suggestion(Extension(pool): Extension<DbPool>, Path(suggestion_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let suggestion_id = parse_id(&suggestion_id)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let (suggestion, place) = match get_place_edit_suggestion_by_id(&mut conn, suggestion_id).await {
+        Ok(suggestion) => suggestion,
+        Err(NotFound) => return Err(AppError::NotFound("Suggestion not found.".into())),
+        Err(err) => return Err(AppError::BadRequest(err.to_string())),
+    };
+
+    let events = get_place_edit_events(&mut conn, suggestion.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "suggestion": ReturnPlaceEditSuggestionDetail { suggestion, place, events }
+    })))
+}
+
+async fn review_suggestion(pool: DbPool, reviewer: User, suggestion_id: String, status: PlaceEditStatus, note: Option<String>) -> Result<Json<Value>, AppError> {
+    let suggestion_id = parse_id(&suggestion_id)?;
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let suggestion = match review_place_edit_suggestion(&mut conn, suggestion_id, reviewer.id, status, note).await {
+        Ok(suggestion) => suggestion,
+        Err(NotFound) => return Err(AppError::NotFound("Suggestion not found.".into())),
+        Err(RollbackTransaction) => return Err(AppError::BadRequest("Suggestion has already been reviewed.".into())),
+        Err(err) => return Err(AppError::BadRequest(format!("Failed to review suggestion. {}", err))),
+    };
+
+    Ok(Json(json!({
+        "suggestion": suggestion
+    })))
+}
+
+/// Approves a pending suggestion, applying its value to the place.
+pub async fn approve_place_edit(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path(suggestion_id): Path<String>,
+    Valid(Json(payload)): Valid<Json<ReviewPlaceEditPayload>>,
+) -> Result<Json<Value>, AppError> {
+    review_suggestion(pool, current_user, suggestion_id, PlaceEditStatus::Approved, payload.note).await
+}
+
+pub async fn reject_place_edit(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Path(suggestion_id): Path<String>,
+    Valid(Json(payload)): Valid<Json<ReviewPlaceEditPayload>>,
+) -> Result<Json<Value>, AppError> {
+    review_suggestion(pool, current_user, suggestion_id, PlaceEditStatus::Rejected, payload.note).await
+}
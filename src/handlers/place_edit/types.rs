@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::models::place_edit::{PlaceEditField, PlaceEditStatus};
+
+#[derive(Deserialize, Serialize)]
+pub struct PlaceEditChange {
+    pub field: String,
+    pub value: Option<String>,
+}
+
+fn validate_changes(changes: &[PlaceEditChange]) -> Result<(), ValidationError> {
+    let mut fields = HashSet::new();
+
+    for change in changes {
+        let Some(field) = PlaceEditField::parse(&change.field) else {
+            return Err(ValidationError::new("changes").with_message("Field must be one of name, formatted_address, formatted_phone_number, business_status or website.".into()));
+        };
+
+        if !fields.insert(field.as_str()) {
+            return Err(ValidationError::new("changes").with_message("Each field can only be changed once.".into()));
+        }
+
+        if change.value.as_ref().is_some_and(|value| value.len() > 500) || !field.is_valid_value(change.value.as_deref()) {
+            return Err(ValidationError::new("changes").with_message(format!("Invalid value for {}.", field.as_str()).into()));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_status(status: &str) -> Result<(), ValidationError> {
+    if PlaceEditStatus::parse(status).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("status").with_message("Status must be one of PENDING, APPROVED or REJECTED.".into()))
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SuggestPlaceEditPayload {
+    #[validate(length(min = 1, max = 5, message = "Changes must list between 1 and 5 fields."), custom(function = "validate_changes"))]
+    pub changes: Vec<PlaceEditChange>,
+
+    #[validate(length(max = 1000, message = "Reason must be at most 1000 characters."))]
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ReviewPlaceEditPayload {
+    #[validate(length(max = 1000, message = "Note must be at most 1000 characters."))]
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct PlaceEditQuery {
+    #[validate(custom(function = "validate_status"))]
+    pub status: Option<String>,
+}
+
+impl PlaceEditQuery {
+    pub fn status(&self) -> PlaceEditStatus {
+        self.status.as_deref().and_then(PlaceEditStatus::parse).unwrap_or(PlaceEditStatus::Pending)
+    }
+}
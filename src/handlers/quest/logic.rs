@@ -0,0 +1,96 @@
+use axum::{Extension, Json};
+use axum_valid::Valid;
+use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+use serde_json::{Value, json};
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    handlers::quest::CreateQuestPayload,
+    models::{
+        quest::{NewQuest, NewQuestStep},
+        user::User,
+    },
+    services::{
+        mission::get_mission_by_code,
+        quest::{create_quest, create_quest_steps, get_quests_progress},
+    },
+    utils::{error_handling::AppError, time::get_user_timezone},
+};
+
+const MAX_QUEST_STEPS: usize = 10;
+
+pub async fn search_quests(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let quests = get_quests_progress(&mut conn, current_user.id, get_user_timezone(&current_user.timezone))
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "quests": quests
+    })))
+}
+
+pub async fn create_new_quest(Extension(pool): Extension<DbPool>, Valid(Json(payload)): Valid<Json<CreateQuestPayload>>) -> Result<Json<Value>, AppError> {
+    if !(1..=MAX_QUEST_STEPS).contains(&payload.steps.len()) {
+        return Err(AppError::BadRequest(format!("A quest needs between 1 and {} steps.", MAX_QUEST_STEPS)));
+    }
+
+    if let (Some(from), Some(until)) = (payload.active_from, payload.active_until)
+        && from >= until
+    {
+        return Err(AppError::BadRequest("Quest must start before it ends.".into()));
+    }
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    get_mission_by_code(&mut conn, &payload.mission_code)
+        .await
+        .map_err(|_| AppError::BadRequest("Reward mission not found.".into()))?;
+
+    let new_quest = NewQuest {
+        code: payload.code,
+        name: payload.name,
+        description: payload.description,
+        period: payload.period,
+        mission_code: payload.mission_code,
+        active_from: payload.active_from,
+        active_until: payload.active_until,
+    };
+
+    let steps = payload.steps;
+
+    let (quest, steps) = conn
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let quest = create_quest(conn, &new_quest).await?;
+
+                let new_steps: Vec<NewQuestStep> = steps
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, step)| NewQuestStep {
+                        quest_id: quest.id,
+                        action: step.action,
+                        place_type: step.place_type,
+                        target_count: step.target_count,
+                        is_distinct: step.is_distinct.unwrap_or(true),
+                        position: position as i32,
+                    })
+                    .collect();
+
+                let mut steps = create_quest_steps(conn, &new_steps).await?;
+
+                steps.sort_by_key(|step| step.position);
+
+                Ok((quest, steps))
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(|err| AppError::BadRequest(format!("Failed to create new quest. {}", err)))?;
+
+    Ok(Json(json!({
+        "quest": quest,
+        "steps": steps
+    })))
+}
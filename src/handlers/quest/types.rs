@@ -0,0 +1,68 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use validator::{Validate, ValidationError};
+
+use crate::models::{mission::MissionPeriod, quest::QuestAction};
+
+fn validate_quest_code(code: &str) -> Result<(), ValidationError> {
+    let is_valid =
+        (3..=30).contains(&code.len()) && code.starts_with(|char: char| char.is_ascii_uppercase()) && code.chars().all(|char| char.is_ascii_uppercase() || char.is_ascii_digit() || char == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("code").with_message("Code must be 3 to 30 uppercase letters, digits or underscores, starting with a letter.".into()))
+    }
+}
+
+fn validate_quest_period(period: &str) -> Result<(), ValidationError> {
+    if MissionPeriod::parse(period).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("period").with_message("Period must be one of daily, weekly, monthly or lifetime.".into()))
+    }
+}
+
+fn validate_quest_action(action: &str) -> Result<(), ValidationError> {
+    if QuestAction::parse(action).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("action").with_message("Action must be VISIT or REVIEW.".into()))
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct QuestStepPayload {
+    #[validate(custom(function = "validate_quest_action"))]
+    pub action: String,
+
+    pub place_type: Option<String>,
+
+    #[validate(range(min = 1, max = 100, message = "Target count must be between 1 and 100."))]
+    pub target_count: i32,
+
+    pub is_distinct: Option<bool>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateQuestPayload {
+    #[validate(custom(function = "validate_quest_code"))]
+    pub code: String,
+
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters."))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    #[validate(custom(function = "validate_quest_period"))]
+    pub period: String,
+
+    pub mission_code: String,
+
+    pub active_from: Option<NaiveDateTime>,
+
+    pub active_until: Option<NaiveDateTime>,
+
+    #[validate(nested)]
+    pub steps: Vec<QuestStepPayload>,
+}
@@ -8,13 +8,22 @@ use crate::{
         cache::{CachePool, get_cache_conn},
         db::{DbPool, get_conn},
     },
-    models::{action_count::UpdateActionCountPayload, review::NewReview, user::User},
+    models::{
+        achievement::AchievementEvent,
+        action_count::UpdateActionCountPayload,
+        quest::QuestAction,
+        review::{MAX_RATING, MIN_RATING, NewReview},
+        user::User,
+    },
     services::{
+        achievement::evaluate_achievements,
         action_count::increase_action_count_by_user,
         mission::do_mission,
+        place::get_place_by_id,
+        quest::evaluate_quests,
         review::{create_review, get_reviews},
     },
-    utils::error_handling::AppError,
+    utils::{error_handling::AppError, time::get_user_timezone},
 };
 
 pub async fn user_review_place(
@@ -23,6 +32,10 @@ pub async fn user_review_place(
     Extension(current_user): Extension<User>,
     Json(mut payload): Json<NewReview>,
 ) -> Result<Json<Value>, AppError> {
+    if !(MIN_RATING..=MAX_RATING).contains(&payload.rating) {
+        return Err(AppError::BadRequest(format!("Rating must be between {} and {}.", MIN_RATING, MAX_RATING)));
+    }
+
     let user_id = current_user.id;
 
     payload.user_id = Some(user_id);
@@ -36,7 +49,9 @@ pub async fn user_review_place(
     let cache_pool_clone = cache_pool.clone();
     let pool_clone = pool.clone();
     let user_id_string = current_user.id.to_string();
+    let tz = get_user_timezone(&current_user.timezone);
     let medias = payload.medias;
+    let place_id = payload.place_id;
 
     task::spawn(async move {
         let mut conn = match get_conn(&pool_clone).await {
@@ -55,19 +70,27 @@ pub async fn user_review_place(
             }
         };
 
+        let place_types: Vec<String> = match get_place_by_id(&mut conn, place_id).await {
+            Ok(place) => place.types.unwrap_or_default().into_iter().flatten().collect(),
+            Err(err) => {
+                eprintln!("Failed to get reviewed place: {}", err);
+                vec![]
+            }
+        };
+
         let media_count = medias.iter().len() as i32;
 
         if media_count > 0 {
             let upload_photo_code = "UPLOAD_PHOTO";
 
-            if let Err(err) = do_mission(&mut conn, &mut cache_conn, &user_id_string, upload_photo_code, Some(media_count)).await {
+            if let Err(err) = do_mission(&mut conn, &mut cache_conn, &user_id_string, upload_photo_code, Some(media_count), &place_types).await {
                 eprintln!("Failed to do mission: {} - {}", upload_photo_code, err)
             };
         }
 
         let review_code = "REVIEW_PLACE";
 
-        if let Err(err) = do_mission(&mut conn, &mut cache_conn, &user_id_string, review_code, None).await {
+        if let Err(err) = do_mission(&mut conn, &mut cache_conn, &user_id_string, review_code, None, &place_types).await {
             eprintln!("Failed to do mission: {} - {}", review_code, err)
         }
 
@@ -76,6 +99,14 @@ pub async fn user_review_place(
         if let Err(err) = increase_action_count_by_user(&mut conn, &user_id_string, &increase_payload).await {
             eprintln!("Failed to increase action count: {} - {}", review_code, err)
         }
+
+        if let Err(err) = evaluate_achievements(&mut conn, user_id, AchievementEvent::ReviewCreated).await {
+            eprintln!("Failed to evaluate achievements: {}", err)
+        }
+
+        if let Err(err) = evaluate_quests(&mut conn, &mut cache_conn, user_id, tz, QuestAction::Review).await {
+            eprintln!("Failed to evaluate quests: {}", err)
+        }
     });
 
     Ok(Json(json!({
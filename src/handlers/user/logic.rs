@@ -1,8 +1,12 @@
 use std::env;
 
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension, Json,
+    extract::{Multipart, Path, Query},
+};
 use axum_valid::Valid;
 use bb8_redis::redis::AsyncCommands;
+use chrono::Utc;
 use lettre::SmtpTransport;
 use rand::Rng;
 use serde_json::{Value, json};
@@ -17,17 +21,38 @@ use crate::{
         cache::{CachePool, get_cache_conn},
         db::{DbPool, get_conn},
         mailer::{mail_template, mailer_send},
-        storage::delete_file,
+        storage::{delete_file, upload_file},
+    },
+    handlers::user::{InvitePayload, ReturnNotificationPreference, ReturnQuietHours, UpdateNotificationPreferencesPayload, UpdateTimezonePayload},
+    models::{
+        check_in_streak::StreakReward,
+        follow::NewFollow,
+        notification_preference::{NOTIFICATION_CATEGORIES, NewNotificationPreference},
+        user::{PhotoField, User, UserQuietHoursChangeset},
+        user_place_access::ReturnVisit,
     },
-    handlers::user::InvitePayload,
-    models::user::User,
     services::{
+        achievement::get_user_achievements,
         action_count::get_action_count_by_user,
+        check_in_streak::{get_check_in_streak_by_user, get_return_check_in_streak, record_check_in},
+        exp_history::get_exp_history_by_user,
         feature_usage::get_feature_usage_by_user,
+        follow::{follow_user, unfollow_user},
+        leaderboard::record_exp_on_leaderboards,
+        level::{get_levels, level_progress},
         mission::do_mission,
-        user::{get_user_by_id, update_user_photo},
+        notification_preference::{get_notification_preferences_by_user, upsert_notification_preference},
+        user::{get_user_by_id, update_user_photo, update_user_quiet_hours, update_user_timezone},
+        user_place_access::{get_visit_stats, get_visits_by_user},
+    },
+    utils::{
+        error_handling::AppError,
+        image::validate_image,
+        jwt::sign_unsubscribe_token,
+        mail_template::invite_user_mail_body,
+        pagination::PaginationQuery,
+        time::{TIMEZONE_CHANGE_COOLDOWN_DAYS, get_today_date, get_user_timezone, is_date_moved_back, is_timezone_change_allowed},
     },
-    utils::{error_handling::AppError, mail_template::invite_user_mail_body},
 };
 
 fn generate_pin_code() -> String {
@@ -40,7 +65,14 @@ fn generate_invite_link(code: &str) -> Result<String, String> {
     Ok(format!("{}/sign-up?invite-code={}", web_url, code))
 }
 
+fn generate_unsubscribe_link(email: &str, category: &str) -> Result<String, String> {
+    let api_url = env::var("API_URL").map_err(|_| "API_URL is missing.".to_string())?;
+    let token = sign_unsubscribe_token(email.to_string(), category.to_string())?;
+    Ok(format!("{}/notifications/unsubscribe?token={}", api_url, token))
+}
+
 pub async fn invite(
+    Extension(pool): Extension<DbPool>,
     Extension(cache_pool): Extension<CachePool>,
     Extension(current_user): Extension<User>,
     Extension(mailer): Extension<SmtpTransport>,
@@ -61,17 +93,23 @@ pub async fn invite(
         .map_err(|e| AppError::BadRequest(format!("Redis error: {}", e)))?;
     let _: () = cache_conn.expire(&key, expire_seconds).await.map_err(|e| AppError::BadRequest(format!("Redis error: {}", e)))?;
 
+    let pool_clone = pool.clone();
+
     task::spawn(async move {
         let retry_strategy = ExponentialBackoff::from_millis(10).map(jitter).take(3);
 
         let result = Retry::spawn(retry_strategy, || async {
+            let mut conn = get_conn(&pool_clone).await?;
+
             let invite_link = generate_invite_link(&code)?;
 
-            let invite_mail_body = invite_user_mail_body(&invite_link)?;
+            let unsubscribe_link = generate_unsubscribe_link(&to_email, "invite")?;
 
-            let mail = mail_template(&to_email, &invite_mail_body)?;
+            let invite_mail_body = invite_user_mail_body(&invite_link, &unsubscribe_link)?;
 
-            mailer_send(&mailer, &mail)
+            let mail = mail_template(&to_email, &invite_mail_body, Some(&unsubscribe_link))?;
+
+            mailer_send(&mut conn, &mailer, &mail, "invite").await
         })
         .await;
 
@@ -96,52 +134,95 @@ pub async fn get_profile(Extension(pool): Extension<DbPool>, Path(user_id): Path
 
     let action_count = get_action_count_by_user(&mut conn, user.id).await.map_err(|_| AppError::NotFound("User not found.".into()))?;
 
+    let levels = get_levels(&mut conn).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let level_progress = level_progress(&levels, user.level.unwrap_or(0), user.exp.unwrap_or(0));
+
+    let streak = get_check_in_streak_by_user(&mut conn, user.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let achievements: Vec<Value> = get_user_achievements(&mut conn, user.id)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?
+        .into_iter()
+        .map(|(user_achievement, achievement)| json!({ "achievement": achievement, "unlocked_at": user_achievement.unlocked_at }))
+        .collect();
+
     Ok(Json(json!({
         "profile":{
     "user": user,
             "feature_usage": feature_usage,
-        "action_count": action_count
+        "action_count": action_count,
+            "level_progress": level_progress,
+            "streak": get_return_check_in_streak(streak.as_ref(), get_today_date(get_user_timezone(&user.timezone))),
+            "achievements": achievements
         }
     })))
 }
 
-pub async fn update_photo(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Json(payload): Json<Value>) -> Result<Json<Value>, AppError> {
-    let field = payload.get("field").ok_or(AppError::BadRequest("Missing field.".into()))?.as_str().unwrap();
-    let photo_url = payload.get("photo_url").ok_or(AppError::BadRequest("Missing photo url.".into()))?.as_str().unwrap();
+fn spawn_delete_file(path: String) {
+    task::spawn(async move {
+        let retry_strategy = ExponentialBackoff::from_millis(10).map(jitter).take(3);
 
-    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+        let result = Retry::spawn(retry_strategy, || async { delete_file(&path).await }).await;
 
-    let user = update_user_photo(&mut conn, &current_user.id.to_string(), field, photo_url)
-        .await
-        .map_err(|_| AppError::BadRequest("Failed to update.".into()))?;
+        if let Err(err) = result {
+            eprintln!("Failed after retries: {}", err);
+        }
+    });
+}
 
-    let field = field.to_string();
+/// Uploads an avatar or cover image for the current user and assigns it.
+///
+/// # Behavior
+/// - Expects the image in the multipart `file` field and validates its format and dimensions.
+/// - Stores it under `{user_id}/{field}/` so users can only point at their own files.
+/// - Deletes the uploaded object again if the user could not be updated.
+/// - Deletes the previous photo once the new one is assigned.
+///
+pub async fn update_photo(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path(field): Path<PhotoField>, mut multipart: Multipart) -> Result<Json<Value>, AppError> {
+    let mut file = None;
+
+    while let Some(part) = multipart.next_field().await.map_err(|err| AppError::BadRequest(err.to_string()))? {
+        if part.name() == Some("file") {
+            let content_type = part.content_type().unwrap_or_default().to_string();
+            let data = part.bytes().await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+            file = Some((content_type, data));
+            break;
+        }
+    }
 
-    task::spawn(async move {
-        let mut path = String::from("");
+    let (content_type, data) = file.ok_or(AppError::BadRequest("Missing file.".into()))?;
 
-        if field == "avatar_url" {
-            if let Some(url) = current_user.avatar_url {
-                path = url.clone();
-            }
-        }
+    let extension = validate_image(&data, &content_type).map_err(AppError::BadRequest)?;
 
-        if field == "cover_url" {
-            if let Some(url) = current_user.cover_url {
-                path = url.clone();
-            }
-        }
+    let user_prefix = format!("{}/", current_user.id);
+    let destination_path = format!("{}{}/{}.{}", user_prefix, field.as_str(), Utc::now().timestamp_millis(), extension);
 
-        if !path.is_empty() {
-            let retry_strategy = ExponentialBackoff::from_millis(10).map(jitter).take(3);
+    upload_file(&destination_path, data.to_vec())
+        .await
+        .map_err(|_| AppError::BadRequest("Failed to upload photo.".into()))?;
 
-            let result = Retry::spawn(retry_strategy, || async { delete_file(&path).await }).await;
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
 
-            if let Err(err) = result {
-                eprintln!("Failed after retries: {}", err);
-            }
+    let mut user = match update_user_photo(&mut conn, &current_user.id.to_string(), field, &destination_path).await {
+        Ok(user) => user,
+        Err(_) => {
+            spawn_delete_file(destination_path);
+            return Err(AppError::BadRequest("Failed to update.".into()));
         }
-    });
+    };
+
+    user.password = String::from("");
+
+    let previous_path = match field {
+        PhotoField::Avatar => current_user.avatar_url,
+        PhotoField::Cover => current_user.cover_url,
+    };
+
+    if let Some(path) = previous_path.filter(|path| path.starts_with(&user_prefix)) {
+        spawn_delete_file(path);
+    }
 
     Ok(Json(json!({
         "user": user
@@ -153,9 +234,202 @@ pub async fn check_in(Extension(pool): Extension<DbPool>, Extension(cache_pool):
 
     let mut cache_conn = get_cache_conn(&cache_pool).await.map_err(AppError::BadRequest)?;
 
-    do_mission(&mut conn, &mut cache_conn, &current_user.id.to_string(), "DAILY_CHECK_IN", None)
+    do_mission(&mut conn, &mut cache_conn, &current_user.id.to_string(), "DAILY_CHECK_IN", None, &[])
         .await
         .map_err(|_| AppError::BadRequest("Failed to check in.".into()))?;
 
+    let today = get_today_date(get_user_timezone(&current_user.timezone));
+
+    let (streak, rewards) = record_check_in(&mut conn, current_user.id, today)
+        .await
+        .map_err(|_| AppError::BadRequest("Failed to update check-in streak.".into()))?;
+
+    for reward in &rewards {
+        if let StreakReward::Exp(amount) = reward
+            && let Err(err) = record_exp_on_leaderboards(&mut cache_conn, &current_user.id.to_string(), *amount).await
+        {
+            eprintln!("Failed to update leaderboards: {}", err);
+        }
+    }
+
+    Ok(Json(json!({
+        "streak": get_return_check_in_streak(Some(&streak), today),
+        "rewards": rewards
+    })))
+}
+
+pub async fn get_notification_preferences(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let stored = get_notification_preferences_by_user(&mut conn, current_user.id)
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let preferences: Vec<ReturnNotificationPreference> = NOTIFICATION_CATEGORIES
+        .iter()
+        .map(|category| match stored.iter().find(|preference| preference.category == *category) {
+            Some(preference) => ReturnNotificationPreference {
+                category: preference.category.clone(),
+                in_app: preference.in_app,
+                push: preference.push,
+                email: preference.email,
+            },
+            None => ReturnNotificationPreference {
+                category: category.to_string(),
+                in_app: true,
+                push: true,
+                email: true,
+            },
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "preferences": preferences,
+        "quiet_hours": ReturnQuietHours {
+            start: current_user.quiet_hours_start,
+            end: current_user.quiet_hours_end,
+        }
+    })))
+}
+
+pub async fn update_notification_preferences(
+    Extension(pool): Extension<DbPool>,
+    Extension(current_user): Extension<User>,
+    Valid(Json(payload)): Valid<Json<UpdateNotificationPreferencesPayload>>,
+) -> Result<Json<Value>, AppError> {
+    if payload.quiet_hours_start.is_some() != payload.quiet_hours_end.is_some() {
+        return Err(AppError::BadRequest("Quiet hours need both a start and an end.".into()));
+    }
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    for preference in payload.preferences {
+        let new_preference = NewNotificationPreference {
+            user_id: current_user.id,
+            category: preference.category,
+            in_app: preference.in_app,
+            push: preference.push,
+            email: preference.email,
+        };
+
+        upsert_notification_preference(&mut conn, &new_preference)
+            .await
+            .map_err(|_| AppError::BadRequest("Failed to update notification preferences.".into()))?;
+    }
+
+    let changes = UserQuietHoursChangeset {
+        quiet_hours_start: payload.quiet_hours_start,
+        quiet_hours_end: payload.quiet_hours_end,
+    };
+
+    let user = update_user_quiet_hours(&mut conn, current_user.id, &changes)
+        .await
+        .map_err(|_| AppError::BadRequest("Failed to update quiet hours.".into()))?;
+
+    get_notification_preferences(Extension(pool), Extension(user)).await
+}
+
+pub async fn get_my_exp_history(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Valid(Query(query)): Valid<Query<PaginationQuery>>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let (exp_history, total) = get_exp_history_by_user(&mut conn, current_user.id, query.limit(), query.offset())
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "exp_history": exp_history,
+        "page": query.page(),
+        "limit": query.limit(),
+        "total": total
+    })))
+}
+
+pub async fn get_my_visits(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Valid(Query(query)): Valid<Query<PaginationQuery>>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let (visits, total) = get_visits_by_user(&mut conn, current_user.id, query.limit(), query.offset())
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    let visits: Vec<ReturnVisit> = visits.into_iter().map(|(visit, place)| ReturnVisit { visit, place }).collect();
+
+    Ok(Json(json!({
+        "visits": visits,
+        "page": query.page(),
+        "limit": query.limit(),
+        "total": total
+    })))
+}
+
+pub async fn get_my_stats(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let stats = get_visit_stats(&mut conn, current_user.id, get_user_timezone(&current_user.timezone))
+        .await
+        .map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+    Ok(Json(json!({
+        "stats": stats
+    })))
+}
+
+pub async fn follow(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path(user_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let followee = get_user_by_id(&mut conn, &user_id).await.map_err(|_| AppError::NotFound("User not found.".into()))?;
+
+    if followee.id == current_user.id {
+        return Err(AppError::BadRequest("You cannot follow yourself.".into()));
+    }
+
+    let new_follow = NewFollow {
+        follower_id: current_user.id,
+        followee_id: followee.id,
+    };
+
+    let follow = follow_user(&mut conn, &new_follow).await.map_err(|_| AppError::BadRequest("Failed to follow user.".into()))?;
+
+    Ok(Json(json!(follow)))
+}
+
+pub async fn unfollow(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Path(user_id): Path<String>) -> Result<Json<Value>, AppError> {
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let followee = get_user_by_id(&mut conn, &user_id).await.map_err(|_| AppError::NotFound("User not found.".into()))?;
+
+    unfollow_user(&mut conn, current_user.id, followee.id)
+        .await
+        .map_err(|_| AppError::BadRequest("Failed to unfollow user.".into()))?;
+
     Ok(Json(json!({})))
 }
+
+pub async fn update_timezone(Extension(pool): Extension<DbPool>, Extension(current_user): Extension<User>, Valid(Json(payload)): Valid<Json<UpdateTimezonePayload>>) -> Result<Json<Value>, AppError> {
+    if payload.timezone == current_user.timezone {
+        return Ok(Json(json!({
+            "timezone": current_user.timezone
+        })));
+    }
+
+    let now = Utc::now();
+
+    if !is_timezone_change_allowed(current_user.timezone_updated_at, now.naive_utc()) {
+        return Err(AppError::BadRequest(format!("Timezone can only be changed once every {} days.", TIMEZONE_CHANGE_COOLDOWN_DAYS)));
+    }
+
+    if is_date_moved_back(get_user_timezone(&current_user.timezone), get_user_timezone(&payload.timezone), now) {
+        return Err(AppError::BadRequest("Timezone cannot move the current date back to a day already used.".into()));
+    }
+
+    let mut conn = get_conn(&pool).await.map_err(AppError::BadRequest)?;
+
+    let user = match update_user_timezone(&mut conn, current_user.id, &payload.timezone, now.naive_utc()).await {
+        Ok(user) => user,
+        Err(diesel::result::Error::NotFound) => return Err(AppError::BadRequest(format!("Timezone can only be changed once every {} days.", TIMEZONE_CHANGE_COOLDOWN_DAYS))),
+        Err(_) => return Err(AppError::BadRequest("Failed to update timezone.".into())),
+    };
+
+    Ok(Json(json!({
+        "timezone": user.timezone
+    })))
+}
@@ -1,8 +1,56 @@
-use serde::Deserialize;
-use validator::Validate;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::{models::notification_preference::NOTIFICATION_CATEGORIES, utils::time::validate_timezone};
+
+fn validate_notification_category(category: &str) -> Result<(), ValidationError> {
+    if NOTIFICATION_CATEGORIES.contains(&category) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("category").with_message("Unknown notification category.".into()))
+    }
+}
 
 #[derive(Deserialize, Validate)]
 pub struct InvitePayload {
     #[validate(email(message = "Please provide a valid email address."))]
     pub email: String,
 }
+
+#[derive(Serialize)]
+pub struct ReturnNotificationPreference {
+    pub category: String,
+    pub in_app: bool,
+    pub push: bool,
+    pub email: bool,
+}
+
+#[derive(Serialize)]
+pub struct ReturnQuietHours {
+    pub start: Option<NaiveTime>,
+    pub end: Option<NaiveTime>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NotificationPreferencePayload {
+    #[validate(custom(function = "validate_notification_category"))]
+    pub category: String,
+    pub in_app: bool,
+    pub push: bool,
+    pub email: bool,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateNotificationPreferencesPayload {
+    #[validate(nested)]
+    pub preferences: Vec<NotificationPreferencePayload>,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateTimezonePayload {
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: String,
+}
@@ -8,9 +8,12 @@ use tokio_retry::{
 
 use crate::{
     config::db::{DbPool, get_conn},
-    handlers::waypoint::OptimizeWaypointPayload,
+    handlers::{collection::get_readable_collection, waypoint::OptimizeWaypointPayload},
     models::user::User,
-    services::feature_usage::{get_feature_usage_by_user, give_usage_count_to_user},
+    services::{
+        collection::get_collection_coordinates,
+        feature_usage::{get_feature_usage_by_user, give_usage_count_to_user},
+    },
     utils::{error_handling::AppError, tsp::nearest_neighbor},
 };
 
@@ -26,7 +29,15 @@ pub async fn optimize_waypoints(Extension(pool): Extension<DbPool>, Extension(cu
     }
 
     let mut origin = payload.origin;
-    let waypoints = payload.waypoints;
+    let mut waypoints = payload.waypoints;
+
+    if let Some(collection_id) = payload.collection_id {
+        let collection = get_readable_collection(&mut conn, collection_id, current_user.id).await?;
+
+        let stops = get_collection_coordinates(&mut conn, collection.id).await.map_err(|err| AppError::BadRequest(err.to_string()))?;
+
+        waypoints.push(stops);
+    }
 
     let mut full_path: Vec<[f64; 2]> = Vec::new();
 
@@ -1,7 +1,11 @@
 use serde::Deserialize;
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 pub struct OptimizeWaypointPayload {
     pub origin: [f64; 2],
+    #[serde(default)]
     pub waypoints: Vec<Vec<[f64; 2]>>,
+    /// Collection whose places are visited as a last group of waypoints.
+    pub collection_id: Option<Uuid>,
 }
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::{task, time};
+use tracing::{error, info};
+
+use crate::{
+    config::db::{DbPool, get_conn},
+    services::place::refresh_trending_scores,
+};
+
+/// Interval between two refreshes of the trending scores.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Spawns a background task refreshing the trending scores of places every `REFRESH_INTERVAL`,
+/// starting immediately.
+pub fn spawn_trending_job(pool: DbPool) {
+    task::spawn(async move {
+        let mut interval = time::interval(REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let mut conn = match get_conn(&pool).await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    error!("Failed to refresh trending scores: {}", err);
+                    continue;
+                }
+            };
+
+            match refresh_trending_scores(&mut conn, Utc::now().naive_utc()).await {
+                Ok(count) => info!("Refreshed trending scores of {} places.", count),
+                Err(err) => error!("Failed to refresh trending scores: {}", err),
+            }
+        }
+    });
+}
@@ -1,6 +1,7 @@
 mod __test__;
 mod config;
 mod handlers;
+mod jobs;
 mod middlewares;
 mod models;
 mod routes;
@@ -0,0 +1,12 @@
+use axum::{Extension, body::Body, extract::Request, middleware::Next, response::Response};
+
+use crate::{models::user::User, utils::error_handling::AppError};
+
+/// Rejects non-admin users. Must run after `authorization_middleware`, which provides the user.
+pub async fn admin_middleware(Extension(current_user): Extension<User>, req: Request, next: Next) -> Result<Response<Body>, AppError> {
+    if !current_user.is_admin {
+        return Err(AppError::Forbidden("Admin access is required.".into()));
+    }
+
+    Ok(next.run(req).await)
+}
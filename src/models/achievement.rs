@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Domain events that may unlock achievements.
+#[derive(Clone, Copy, Debug)]
+pub enum AchievementEvent {
+    ReviewCreated,
+    PlaceAccessed,
+    LeveledUp,
+    FriendInvited,
+}
+
+impl AchievementEvent {
+    /// The `achievements.metric` values affected by this event.
+    pub fn metrics(&self) -> &'static [&'static str] {
+        match self {
+            AchievementEvent::ReviewCreated => &["REVIEW_COUNT"],
+            AchievementEvent::PlaceAccessed => &["PLACE_VISITS"],
+            AchievementEvent::LeveledUp => &["LEVEL"],
+            AchievementEvent::FriendInvited => &["INVITE_COUNT"],
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::achievements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Achievement {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub metric: String,
+    /// Only counts visits to places of this type when `metric` is `PLACE_VISITS`.
+    pub place_type: Option<String>,
+    pub threshold: i32,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Clone, Serialize)]
+#[diesel(table_name = crate::schema::user_achievements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserAchievement {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub achievement_id: Uuid,
+    pub unlocked_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::user_achievements)]
+pub struct NewUserAchievement {
+    pub user_id: Uuid,
+    pub achievement_id: Uuid,
+}
+
+#[derive(Serialize)]
+pub struct ReturnAchievement {
+    #[serde(flatten)]
+    pub achievement: Achievement,
+    pub progress: i64,
+    pub unlocked_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,36 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A node of the place taxonomy, e.g. "cafe" under "food_and_drink". Categories are what
+/// `user_place_access.type` records and what searches, quests and achievements match.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::categories)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Category {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub parent_code: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Classifies places of Google type `google_type` under `category_code`.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::category_google_types)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CategoryGoogleType {
+    pub google_type: String,
+    pub category_code: String,
+    pub priority: i32,
+}
+
+#[derive(Serialize)]
+pub struct ReturnCategory {
+    #[serde(flatten)]
+    pub category: Category,
+    /// Google types classified directly under the category.
+    pub google_types: Vec<String>,
+    pub children: Vec<ReturnCategory>,
+}
@@ -0,0 +1,49 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Freeze tokens a user can hold at once.
+pub const MAX_FREEZE_TOKENS: i32 = 2;
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "type", content = "amount", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StreakReward {
+    Exp(i32),
+    RouteCalculation(i32),
+    FreezeToken(i32),
+}
+
+#[derive(Queryable, Selectable, Serialize, Clone)]
+#[diesel(table_name = crate::schema::check_in_streaks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CheckInStreak {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub current_streak: i32,
+    pub longest_streak: i32,
+    pub last_check_in_date: Option<NaiveDate>,
+    pub freeze_tokens: i32,
+    pub created_at: NaiveDateTime,
+    /// Date of the first check-in of the current streak.
+    pub started_on: Option<NaiveDate>,
+}
+
+#[derive(Insertable, AsChangeset, Debug, PartialEq)]
+#[diesel(table_name = crate::schema::check_in_streaks)]
+pub struct NewCheckInStreak {
+    pub user_id: Uuid,
+    pub current_streak: i32,
+    pub longest_streak: i32,
+    pub last_check_in_date: Option<NaiveDate>,
+    pub freeze_tokens: i32,
+    pub started_on: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+pub struct ReturnCheckInStreak {
+    pub current_streak: i32,
+    pub longest_streak: i32,
+    pub last_check_in_date: Option<NaiveDate>,
+    pub freeze_tokens: i32,
+}
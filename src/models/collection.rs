@@ -0,0 +1,111 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::place::Place;
+
+/// Who can see a collection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollectionVisibility {
+    /// Listed on the owner's profile and readable by anyone.
+    Public,
+    /// Readable only by the owner.
+    Private,
+    /// Readable by anyone with its id, but not listed on the owner's profile.
+    Unlisted,
+}
+
+impl CollectionVisibility {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "PUBLIC" => Some(CollectionVisibility::Public),
+            "PRIVATE" => Some(CollectionVisibility::Private),
+            "UNLISTED" => Some(CollectionVisibility::Unlisted),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollectionVisibility::Public => "PUBLIC",
+            CollectionVisibility::Private => "PRIVATE",
+            CollectionVisibility::Unlisted => "UNLISTED",
+        }
+    }
+}
+
+/// A named list of places saved by a user, e.g. "Hanoi coffee".
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::collections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Collection {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Collection {
+    /// Whether the user `user_id` may read the collection.
+    pub fn is_readable_by(&self, user_id: Uuid) -> bool {
+        self.user_id == user_id || CollectionVisibility::parse(&self.visibility) != Some(CollectionVisibility::Private)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::collections)]
+pub struct NewCollection {
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: String,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::collections)]
+pub struct UpdateCollection {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub visibility: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A place saved in a collection; items are ordered by ascending `position`.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::collection_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CollectionItem {
+    pub id: Uuid,
+    pub collection_id: Uuid,
+    pub place_id: Uuid,
+    pub position: i32,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::collection_items)]
+pub struct NewCollectionItem {
+    pub collection_id: Uuid,
+    pub place_id: Uuid,
+    pub position: i32,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ReturnCollectionItem {
+    #[serde(flatten)]
+    pub item: CollectionItem,
+    pub place: Place,
+}
+
+#[derive(Serialize)]
+pub struct ReturnCollection {
+    #[serde(flatten)]
+    pub collection: Collection,
+    pub items: Vec<ReturnCollectionItem>,
+}
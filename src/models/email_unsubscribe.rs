@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable, Serialize)]
+#[diesel(table_name = crate::schema::email_unsubscribes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct EmailUnsubscribe {
+    pub id: Uuid,
+    pub email: String,
+    pub category: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::email_unsubscribes)]
+pub struct NewEmailUnsubscribe {
+    pub email: String,
+    pub category: String,
+}
@@ -0,0 +1,39 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A time-limited EXP boost, e.g. weekend double EXP. Applies to every mission unless narrowed
+/// to `mission_code` and/or to places of `place_type`, a Google type or a category code.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::exp_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ExpEvent {
+    pub id: Uuid,
+    pub name: String,
+    pub multiplier: f64,
+    pub mission_code: Option<String>,
+    pub place_type: Option<String>,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl ExpEvent {
+    /// Whether the event boosts mission `code` performed on a place tagged `place_types`, its Google
+    /// types and categories as given by `get_place_type_tags`.
+    pub fn applies_to(&self, code: &str, place_types: &[String]) -> bool {
+        self.mission_code.as_deref().is_none_or(|mission_code| mission_code == code) && self.place_type.as_ref().is_none_or(|place_type| place_types.contains(place_type))
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::exp_events)]
+pub struct NewExpEvent {
+    pub name: String,
+    pub multiplier: f64,
+    pub mission_code: Option<String>,
+    pub place_type: Option<String>,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+}
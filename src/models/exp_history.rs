@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable, Serialize)]
+#[diesel(table_name = crate::schema::exp_history)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ExpHistory {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub source: Option<String>,
+    pub amount: Option<i32>,
+    pub created_at: NaiveDateTime,
+    /// Product of the EXP event multipliers applied to the grant.
+    pub multiplier: f64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::exp_history)]
+pub struct NewExpHistory {
+    pub user_id: Option<Uuid>,
+    pub source: Option<String>,
+    pub amount: Option<i32>,
+    pub multiplier: f64,
+}
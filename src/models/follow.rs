@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable, Serialize)]
+#[diesel(table_name = crate::schema::follows)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Follow {
+    pub id: Uuid,
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::follows)]
+pub struct NewFollow {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+}
@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+/// A reward granted on top of EXP, declared by `gift_reward_type` / `gift_reward_count` on
+/// missions and `reward_type` / `reward_count` on levels.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GiftReward {
+    /// Route calculation credits.
+    RouteCalculation(i32),
+    /// Days of premium access.
+    PremiumDays(i32),
+    /// Achievement code to unlock.
+    Badge(String),
+}
+
+impl GiftReward {
+    /// Builds the reward from its stored columns; `code` is only used by `BADGE` rewards.
+    /// Returns `None` for unknown types, or when the count or code the type needs is missing.
+    pub fn parse(reward_type: Option<&str>, count: Option<i32>, code: Option<&str>) -> Option<Self> {
+        match reward_type? {
+            "ROUTE_CALCULATION" => count.filter(|count| *count > 0).map(GiftReward::RouteCalculation),
+            "PREMIUM_DAYS" => count.filter(|count| *count > 0).map(GiftReward::PremiumDays),
+            "BADGE" => code.map(|code| GiftReward::Badge(code.to_string())),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A sign-up through an invite of `inviter_id`.
+#[derive(Queryable, Selectable, Serialize)]
+#[diesel(table_name = crate::schema::invites)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Invite {
+    pub id: Uuid,
+    pub inviter_id: Uuid,
+    pub invitee_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::invites)]
+pub struct NewInvite {
+    pub inviter_id: Uuid,
+    pub invitee_id: Option<Uuid>,
+}
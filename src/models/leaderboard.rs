@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardPeriod {
+    Global,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Serialize)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub user_id: Uuid,
+    pub exp: i64,
+    pub level: Option<i32>,
+    pub avatar_url: Option<String>,
+}
@@ -0,0 +1,36 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+/// EXP needed per level when the `levels` table is empty.
+pub const DEFAULT_EXP_REQUIRED: i32 = 200;
+
+/// A step of the level curve: `exp_required` is the EXP needed to advance from `level` to the
+/// next one, and the reward is granted when `level` is reached.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::levels)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Level {
+    pub level: i32,
+    pub exp_required: i32,
+    pub reward_type: Option<String>,
+    pub reward_count: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LevelProgress {
+    pub level: i32,
+    pub exp: i32,
+    pub exp_required: i32,
+    pub exp_to_next_level: i32,
+    pub progress: f64,
+}
+
+/// Result of applying a user's accumulated EXP to the level curve.
+#[derive(Debug, PartialEq)]
+pub struct LevelUp {
+    pub level: i32,
+    pub exp: i32,
+    pub reached: Vec<i32>,
+}
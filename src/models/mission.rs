@@ -3,6 +3,27 @@ use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Window over which `max_per_day` completions of a mission are counted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MissionPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+    Lifetime,
+}
+
+impl MissionPeriod {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(MissionPeriod::Daily),
+            "weekly" => Some(MissionPeriod::Weekly),
+            "monthly" => Some(MissionPeriod::Monthly),
+            "lifetime" => Some(MissionPeriod::Lifetime),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Queryable, Selectable, Clone, Serialize, Debug)]
 #[diesel(table_name = crate::schema::missions)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -14,8 +35,35 @@ pub struct Mission {
     pub exp_reward: i32,
     pub gift_reward_count: Option<i32>,
     pub gift_reward_type: Option<String>,
+    /// Maximum completions per `period`.
     pub max_per_day: Option<i32>,
     pub created_at: NaiveDateTime,
+    pub period: String,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    /// Achievement code unlocked by a `BADGE` gift.
+    pub gift_reward_code: Option<String>,
+    pub is_disabled: bool,
+}
+
+impl Mission {
+    /// Whether the mission can be completed at `now`: enabled and inside its active window.
+    pub fn is_active_at(&self, now: NaiveDateTime) -> bool {
+        !self.is_disabled && self.active_from.is_none_or(|from| from <= now) && self.active_until.is_none_or(|until| now < until)
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReturnMissionProgress {
+    #[serde(flatten)]
+    pub mission: Mission,
+    pub is_active: bool,
+    /// Completions in the current period.
+    pub completed_count: i32,
+    /// Completions left in the current period, `None` when the mission is uncapped.
+    pub remaining: Option<i32>,
+    /// When the current period ends (UTC), `None` for lifetime missions.
+    pub resets_at: Option<NaiveDateTime>,
 }
 
 #[derive(Insertable, Deserialize, Debug)]
@@ -28,4 +76,31 @@ pub struct NewMission {
     pub gift_reward_count: Option<i32>,
     pub gift_reward_type: Option<String>,
     pub max_per_day: Option<i32>,
+    pub period: Option<String>,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub gift_reward_code: Option<String>,
+}
+
+/// Partial update of a mission; `None` fields are left unchanged and `Some(None)` fields are
+/// cleared.
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::missions)]
+pub struct UpdateMission {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub exp_reward: Option<i32>,
+    pub gift_reward_count: Option<Option<i32>>,
+    pub gift_reward_type: Option<Option<String>>,
+    pub gift_reward_code: Option<Option<String>>,
+    pub max_per_day: Option<Option<i32>>,
+    pub period: Option<String>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::missions)]
+#[diesel(treat_none_as_null = true)]
+pub struct MissionScheduleChangeset {
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
 }
@@ -1,7 +1,24 @@
+pub mod achievement;
 pub mod action_count;
+pub mod category;
+pub mod check_in_streak;
+pub mod collection;
+pub mod email_unsubscribe;
+pub mod exp_event;
+pub mod exp_history;
 pub mod feature_usage;
+pub mod follow;
+pub mod gift_reward;
+pub mod invite;
+pub mod leaderboard;
+pub mod level;
 pub mod mission;
+pub mod notification_preference;
+pub mod opening_hours;
 pub mod place;
+pub mod place_edit;
+pub mod quest;
+pub mod recommendation;
 pub mod review;
 pub mod subscription;
 pub mod user;
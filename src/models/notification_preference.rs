@@ -0,0 +1,29 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const NOTIFICATION_CATEGORIES: &[&str] = &["account", "invite", "mission", "marketing"];
+
+#[derive(Queryable, Selectable, Serialize, Clone)]
+#[diesel(table_name = crate::schema::notification_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationPreference {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub category: String,
+    pub in_app: bool,
+    pub push: bool,
+    pub email: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::notification_preferences)]
+pub struct NewNotificationPreference {
+    pub user_id: Uuid,
+    pub category: String,
+    pub in_app: bool,
+    pub push: bool,
+    pub email: bool,
+}
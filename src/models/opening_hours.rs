@@ -0,0 +1,87 @@
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A day and local time of the week, in Google's format: `day` from 0 (Sunday) to 6 (Saturday) and
+/// `time` as `HHMM`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OpeningTime {
+    pub day: u8,
+    pub time: String,
+}
+
+impl OpeningTime {
+    /// Minutes since Sunday 00:00, or `None` when the day or time is invalid.
+    pub fn minute_of_week(&self) -> Option<u32> {
+        if self.day > 6 || self.time.len() != 4 || !self.time.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let hour: u32 = self.time[..2].parse().ok()?;
+        let minute: u32 = self.time[2..].parse().ok()?;
+
+        if hour > 23 || minute > 59 {
+            return None;
+        }
+
+        Some(self.day as u32 * 24 * 60 + hour * 60 + minute)
+    }
+}
+
+/// An opening of a place; without `close`, the place is open around the clock.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OpeningPeriod {
+    pub open: OpeningTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close: Option<OpeningTime>,
+}
+
+/// Weekly opening hours of a place, stored in `places.opening_hours`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OpeningHours {
+    pub periods: Vec<OpeningPeriod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekday_text: Vec<String>,
+}
+
+impl OpeningHours {
+    /// Parses Google's `opening_hours` object. `open_now` is dropped since it is only valid when
+    /// fetched.
+    ///
+    /// # Returns
+    /// `None` when there are no periods or any period has an invalid day or time.
+    ///
+    pub fn from_google(value: &Value) -> Option<Self> {
+        let opening_hours: OpeningHours = serde_json::from_value(value.clone()).ok()?;
+
+        let is_valid = !opening_hours.periods.is_empty()
+            && opening_hours
+                .periods
+                .iter()
+                .all(|period| period.open.minute_of_week().is_some() && period.close.as_ref().is_none_or(|close| close.minute_of_week().is_some()));
+
+        is_valid.then_some(opening_hours)
+    }
+
+    /// Whether the place is open at the local time `local`.
+    ///
+    /// # Behavior
+    /// Periods closing earlier in the week than they open, e.g. Saturday 22:00 to Sunday 02:00,
+    /// wrap around the end of the week.
+    ///
+    pub fn is_open_at(&self, local: NaiveDateTime) -> bool {
+        let now = local.weekday().num_days_from_sunday() * 24 * 60 + local.hour() * 60 + local.minute();
+
+        self.periods.iter().any(|period| {
+            let Some(open) = period.open.minute_of_week() else {
+                return false;
+            };
+
+            let Some(close) = period.close.as_ref().and_then(|close| close.minute_of_week()) else {
+                return period.close.is_none();
+            };
+
+            if open <= close { open <= now && now < close } else { now >= open || now < close }
+        })
+    }
+}
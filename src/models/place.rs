@@ -1,9 +1,11 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, TimeDelta};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::models::{opening_hours::OpeningHours, place_edit::PlaceEditField};
+
 #[derive(Queryable, Selectable, Clone, Serialize, Debug)]
 #[diesel(table_name = crate::schema::places)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -31,6 +33,55 @@ pub struct Place {
     pub plus_code: Option<Value>,
     pub created_at: NaiveDateTime,
     pub range_time_view_count: i32,
+    /// Generated from `geometry.location`.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// View count decayed by age, refreshed periodically by the trending job.
+    pub trending_score: f64,
+    /// Last time the Google data of the place was refreshed.
+    pub updated_at: NaiveDateTime,
+    /// Parsed from Google's `opening_hours`, see `OpeningHours`.
+    pub opening_hours: Option<Value>,
+    /// Fields set by approved edit suggestions, which Google refreshes no longer overwrite.
+    pub overridden_fields: Vec<String>,
+}
+
+impl Place {
+    /// Whether the place is open at the UTC time `at`, from its opening hours and `utc_offset` in
+    /// minutes, or `None` when either is unknown.
+    pub fn is_open_at(&self, at: NaiveDateTime) -> Option<bool> {
+        let offset: i64 = self.utc_offset.as_deref()?.trim().parse().ok()?;
+
+        let opening_hours = OpeningHours::from_google(self.opening_hours.as_ref()?)?;
+
+        Some(opening_hours.is_open_at(at + TimeDelta::minutes(offset)))
+    }
+}
+
+/// Optional filters of place searches.
+#[derive(Default, Clone, Copy)]
+pub struct PlaceFilter<'a> {
+    /// Only places having one of these Google types.
+    pub place_types: Option<&'a [String]>,
+    /// Only places known to be open at this UTC time.
+    pub open_at: Option<NaiveDateTime>,
+}
+
+/// Views of a place during the hour starting at `bucket_start`.
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::place_view_buckets)]
+pub struct NewPlaceViewBucket {
+    pub place_id: Uuid,
+    pub bucket_start: NaiveDateTime,
+    pub view_count: i32,
+}
+
+#[derive(Serialize)]
+pub struct ReturnNearbyPlace {
+    #[serde(flatten)]
+    pub place: Place,
+    /// Distance from the searched point in meters.
+    pub distance: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -74,4 +125,75 @@ pub struct NewPlace {
     pub types: Option<Vec<Option<String>>>,
     pub address_components: Option<Value>,
     pub plus_code: Option<Value>,
+    pub opening_hours: Option<Value>,
+}
+
+/// Fields of a place that change over time on Google's side. `None` fields are left unchanged.
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::places)]
+pub struct PlaceChangeset {
+    pub name: Option<String>,
+    pub formatted_address: Option<String>,
+    pub formatted_phone_number: Option<String>,
+    pub business_status: Option<String>,
+    pub adr_address: Option<String>,
+    pub icon: Option<String>,
+    pub icon_background_color: Option<String>,
+    pub icon_mask_base_uri: Option<String>,
+    pub rating: Option<f64>,
+    pub user_ratings_total: Option<i32>,
+    pub url: Option<String>,
+    pub website: Option<String>,
+    pub vicinity: Option<String>,
+    pub utc_offset: Option<String>,
+    pub reference: Option<String>,
+    pub geometry: Option<Value>,
+    pub types: Option<Vec<Option<String>>>,
+    pub address_components: Option<Value>,
+    pub plus_code: Option<Value>,
+    pub opening_hours: Option<Value>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl PlaceChangeset {
+    /// Leaves the columns named in `fields` unchanged.
+    pub fn without_fields(mut self, fields: &[String]) -> Self {
+        for field in fields.iter().filter_map(|field| PlaceEditField::parse(field)) {
+            match field {
+                PlaceEditField::Name => self.name = None,
+                PlaceEditField::FormattedAddress => self.formatted_address = None,
+                PlaceEditField::FormattedPhoneNumber => self.formatted_phone_number = None,
+                PlaceEditField::BusinessStatus => self.business_status = None,
+                PlaceEditField::Website => self.website = None,
+            }
+        }
+
+        self
+    }
+
+    pub fn from_new_place(place: &NewPlace, updated_at: NaiveDateTime) -> Self {
+        PlaceChangeset {
+            name: Some(place.name.clone()),
+            formatted_address: place.formatted_address.clone(),
+            formatted_phone_number: place.formatted_phone_number.clone(),
+            business_status: place.business_status.clone(),
+            adr_address: place.adr_address.clone(),
+            icon: place.icon.clone(),
+            icon_background_color: place.icon_background_color.clone(),
+            icon_mask_base_uri: place.icon_mask_base_uri.clone(),
+            rating: place.rating,
+            user_ratings_total: place.user_ratings_total,
+            url: place.url.clone(),
+            website: place.website.clone(),
+            vicinity: place.vicinity.clone(),
+            utc_offset: place.utc_offset.clone(),
+            reference: place.reference.clone(),
+            geometry: place.geometry.clone(),
+            types: place.types.clone(),
+            address_components: place.address_components.clone(),
+            plus_code: place.plus_code.clone(),
+            opening_hours: place.opening_hours.clone(),
+            updated_at,
+        }
+    }
 }
@@ -0,0 +1,158 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::place::Place;
+
+/// Business statuses accepted for `PlaceEditField::BusinessStatus`, as used by Google.
+pub const BUSINESS_STATUSES: &[&str] = &["OPERATIONAL", "CLOSED_TEMPORARILY", "CLOSED_PERMANENTLY"];
+
+/// Place field users can suggest edits for, named after its column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaceEditField {
+    Name,
+    FormattedAddress,
+    FormattedPhoneNumber,
+    BusinessStatus,
+    Website,
+}
+
+impl PlaceEditField {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "name" => Some(PlaceEditField::Name),
+            "formatted_address" => Some(PlaceEditField::FormattedAddress),
+            "formatted_phone_number" => Some(PlaceEditField::FormattedPhoneNumber),
+            "business_status" => Some(PlaceEditField::BusinessStatus),
+            "website" => Some(PlaceEditField::Website),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaceEditField::Name => "name",
+            PlaceEditField::FormattedAddress => "formatted_address",
+            PlaceEditField::FormattedPhoneNumber => "formatted_phone_number",
+            PlaceEditField::BusinessStatus => "business_status",
+            PlaceEditField::Website => "website",
+        }
+    }
+
+    /// Current value of the field on `place`.
+    pub fn get(&self, place: &Place) -> Option<String> {
+        match self {
+            PlaceEditField::Name => Some(place.name.clone()),
+            PlaceEditField::FormattedAddress => place.formatted_address.clone(),
+            PlaceEditField::FormattedPhoneNumber => place.formatted_phone_number.clone(),
+            PlaceEditField::BusinessStatus => place.business_status.clone(),
+            PlaceEditField::Website => place.website.clone(),
+        }
+    }
+
+    /// Whether `value` is acceptable for the field; only the name cannot be cleared.
+    pub fn is_valid_value(&self, value: Option<&str>) -> bool {
+        match (self, value) {
+            (PlaceEditField::Name, None) => false,
+            (PlaceEditField::BusinessStatus, Some(value)) => BUSINESS_STATUSES.contains(&value),
+            (_, Some(value)) => !value.trim().is_empty(),
+            (_, None) => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaceEditStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl PlaceEditStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "PENDING" => Some(PlaceEditStatus::Pending),
+            "APPROVED" => Some(PlaceEditStatus::Approved),
+            "REJECTED" => Some(PlaceEditStatus::Rejected),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaceEditStatus::Pending => "PENDING",
+            PlaceEditStatus::Approved => "APPROVED",
+            PlaceEditStatus::Rejected => "REJECTED",
+        }
+    }
+}
+
+/// A user's proposed value for one field of a place, waiting for or having passed moderation.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::place_edit_suggestions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PlaceEditSuggestion {
+    pub id: Uuid,
+    pub place_id: Uuid,
+    pub user_id: Uuid,
+    pub field: String,
+    pub proposed_value: Option<String>,
+    pub reason: Option<String>,
+    pub status: String,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub review_note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::place_edit_suggestions)]
+pub struct NewPlaceEditSuggestion {
+    pub place_id: Uuid,
+    pub user_id: Uuid,
+    pub field: String,
+    pub proposed_value: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// An entry of the audit trail of a suggestion; `action` is the status it moved to.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::place_edit_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PlaceEditEvent {
+    pub id: Uuid,
+    pub suggestion_id: Uuid,
+    pub actor_id: Uuid,
+    pub action: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::place_edit_events)]
+pub struct NewPlaceEditEvent {
+    pub suggestion_id: Uuid,
+    pub actor_id: Uuid,
+    pub action: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ReturnPlaceEditSuggestion {
+    #[serde(flatten)]
+    pub suggestion: PlaceEditSuggestion,
+    pub place: Place,
+}
+
+#[derive(Serialize)]
+pub struct ReturnPlaceEditSuggestionDetail {
+    #[serde(flatten)]
+    pub suggestion: PlaceEditSuggestion,
+    pub place: Place,
+    pub events: Vec<PlaceEditEvent>,
+}
@@ -0,0 +1,137 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// User action a quest step counts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuestAction {
+    /// Accessing a place, recorded in `user_place_access`.
+    Visit,
+    /// Reviewing a place.
+    Review,
+}
+
+impl QuestAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "VISIT" => Some(QuestAction::Visit),
+            "REVIEW" => Some(QuestAction::Review),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestAction::Visit => "VISIT",
+            QuestAction::Review => "REVIEW",
+        }
+    }
+}
+
+/// A multi-step goal, e.g. visiting 3 different cafés in a week. Completing every step once per
+/// `period` performs the mission `mission_code`, which grants the rewards.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::quests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Quest {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub period: String,
+    pub mission_code: String,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl Quest {
+    pub fn is_active_at(&self, now: NaiveDateTime) -> bool {
+        self.active_from.is_none_or(|from| from <= now) && self.active_until.is_none_or(|until| now < until)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::quests)]
+pub struct NewQuest {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub period: String,
+    pub mission_code: String,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+}
+
+/// A step is met once the user performed `action` on `target_count` places of `place_type` (any
+/// type when `None`) during the quest period; `is_distinct` counts each place once. Steps are
+/// ordered by ascending `position`.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::quest_steps)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct QuestStep {
+    pub id: Uuid,
+    pub quest_id: Uuid,
+    pub action: String,
+    pub place_type: Option<String>,
+    pub target_count: i32,
+    pub is_distinct: bool,
+    pub created_at: NaiveDateTime,
+    pub position: i32,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = crate::schema::quest_steps)]
+pub struct NewQuestStep {
+    #[serde(skip)]
+    pub quest_id: Uuid,
+    pub action: String,
+    pub place_type: Option<String>,
+    pub target_count: i32,
+    pub is_distinct: bool,
+    #[serde(skip)]
+    pub position: i32,
+}
+
+/// Progress of a user on a quest in one period; `progress` holds the count of each step, in step
+/// order.
+#[derive(Queryable, Selectable, Clone, Serialize, Debug)]
+#[diesel(table_name = crate::schema::user_quests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserQuest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub quest_id: Uuid,
+    pub period_key: String,
+    pub progress: Vec<i32>,
+    pub completed_at: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Progress update of a user quest; `completed_at` is only set by claiming the completion, see
+/// `claim_user_quest_completion`.
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::user_quests)]
+pub struct NewUserQuest {
+    pub user_id: Uuid,
+    pub quest_id: Uuid,
+    pub period_key: String,
+    pub progress: Vec<i32>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct ReturnQuestStep {
+    #[serde(flatten)]
+    pub step: QuestStep,
+    pub progress: i32,
+}
+
+#[derive(Serialize)]
+pub struct ReturnQuest {
+    #[serde(flatten)]
+    pub quest: Quest,
+    pub steps: Vec<ReturnQuestStep>,
+    pub completed_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+use crate::models::{category::Category, place::Place};
+
+/// What a nearby place is scored on when recommending it.
+pub struct RecommendationCandidate<'a> {
+    /// Resolved category of the place, if any.
+    pub category: Option<&'a Category>,
+    pub rating: Option<f64>,
+    pub user_ratings_total: Option<i32>,
+    pub trending_score: f64,
+    /// Distance from the searched point in meters.
+    pub distance: f64,
+}
+
+#[derive(Serialize)]
+pub struct ReturnRecommendedPlace {
+    #[serde(flatten)]
+    pub place: Place,
+    /// Distance from the searched point in meters.
+    pub distance: f64,
+    pub score: f64,
+    /// Why the place is recommended, e.g. "Because you visit cafes".
+    pub reason: String,
+}
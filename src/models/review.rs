@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+/// Lowest rating a review can give, in stars.
+pub const MIN_RATING: f64 = 1.0;
+/// Highest rating a review can give, in stars.
+pub const MAX_RATING: f64 = 5.0;
+
 #[derive(Queryable, Selectable, Serialize)]
 #[diesel(table_name = crate::schema::reviews)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -38,3 +43,15 @@ pub struct NewReview {
     pub time: Option<i32>,
     pub medias: Option<Vec<Option<Value>>>,
 }
+
+/// Statistics of the reviews written by our users on a place, as opposed to the imported Google
+/// reviews.
+#[derive(Serialize, Default)]
+pub struct ReviewStats {
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+    /// Count of reviews per star, from 1 to 5 stars.
+    pub rating_histogram: [i64; 5],
+    /// Medias of the most recent reviews, newest first.
+    pub recent_photos: Vec<Value>,
+}
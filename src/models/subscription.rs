@@ -3,6 +3,9 @@ use diesel::prelude::*;
 use serde::Serialize;
 use uuid::Uuid;
 
+/// `app` of the subscriptions granted by `PREMIUM_DAYS` gifts, which count for every app.
+pub const GIFT_SUBSCRIPTION_APP: &str = "gift";
+
 #[derive(Queryable, Selectable, Serialize, Clone)]
 #[diesel(table_name = crate::schema::subscriptions)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
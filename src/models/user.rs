@@ -1,6 +1,6 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, NaiveTime};
 use diesel::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Queryable, Selectable, Clone, Serialize)]
@@ -15,6 +15,13 @@ pub struct User {
     pub created_at: NaiveDateTime,
     pub avatar_url: Option<String>,
     pub cover_url: Option<String>,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub is_admin: bool,
+    /// IANA timezone name used for daily resets.
+    pub timezone: String,
+    /// When the timezone was last changed, for the change cooldown.
+    pub timezone_updated_at: Option<NaiveDateTime>,
 }
 
 #[derive(Insertable)]
@@ -24,6 +31,23 @@ pub struct NewUser {
     pub password: String,
     pub avatar_url: Option<String>,
     pub cover_url: Option<String>,
+    pub timezone: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PhotoField {
+    Avatar,
+    Cover,
+}
+
+impl PhotoField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PhotoField::Avatar => "avatar",
+            PhotoField::Cover => "cover",
+        }
+    }
 }
 
 #[derive(AsChangeset, Default)]
@@ -32,3 +56,10 @@ pub struct UserPhotoChangeset {
     pub avatar_url: Option<String>,
     pub cover_url: Option<String>,
 }
+
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::users, treat_none_as_null = true)]
+pub struct UserQuietHoursChangeset {
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+}
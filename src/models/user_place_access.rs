@@ -1,8 +1,13 @@
-use chrono::NaiveDateTime;
-use diesel::prelude::*;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Text},
+};
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::models::place::Place;
+
 #[derive(Queryable, Selectable, Serialize)]
 #[diesel(table_name = crate::schema::user_place_access)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -12,6 +17,8 @@ pub struct UserPlaceAccess {
     pub place_id: Uuid,
     pub type_: String,
     pub created_at: NaiveDateTime,
+    /// Date of the visit in the user's timezone. A place counts once per user per day.
+    pub visited_on: NaiveDate,
 }
 
 #[derive(Insertable)]
@@ -20,4 +27,46 @@ pub struct NewUserPlaceAccess {
     pub user_id: Uuid,
     pub place_id: Uuid,
     pub type_: String,
+    pub visited_on: NaiveDate,
+}
+
+#[derive(Serialize)]
+pub struct ReturnVisit {
+    #[serde(flatten)]
+    pub visit: UserPlaceAccess,
+    pub place: Place,
+}
+
+#[derive(Serialize)]
+pub struct VisitTypeCount {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub visit_count: i64,
+}
+
+#[derive(Serialize)]
+pub struct MostVisitedPlace {
+    pub place: Place,
+    pub visit_count: i64,
+}
+
+/// Visits of a user during a calendar month of their timezone, formatted as `YYYY-MM`.
+#[derive(QueryableByName, Serialize)]
+pub struct MonthlyVisits {
+    #[diesel(sql_type = Text)]
+    pub month: String,
+    #[diesel(sql_type = BigInt)]
+    pub visit_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub distinct_places: i64,
+}
+
+#[derive(Serialize)]
+pub struct VisitStats {
+    pub total_visits: i64,
+    pub distinct_places: i64,
+    pub by_type: Vec<VisitTypeCount>,
+    pub most_visited: Vec<MostVisitedPlace>,
+    /// Months with at least one visit among the last 12, oldest first.
+    pub monthly: Vec<MonthlyVisits>,
 }
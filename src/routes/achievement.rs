@@ -0,0 +1,7 @@
+use axum::{Router, middleware, routing::get};
+
+use crate::{handlers::achievement::search_achievements, middlewares::auth::authorization_middleware};
+
+pub fn achievement_routes() -> Router {
+    Router::new().route("/", get(search_achievements)).layer(middleware::from_fn(authorization_middleware))
+}
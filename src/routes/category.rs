@@ -0,0 +1,7 @@
+use axum::{Router, middleware, routing::get};
+
+use crate::{handlers::category::search_categories, middlewares::auth::authorization_middleware};
+
+pub fn category_routes() -> Router {
+    Router::new().route("/", get(search_categories)).layer(middleware::from_fn(authorization_middleware))
+}
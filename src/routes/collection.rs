@@ -0,0 +1,19 @@
+use axum::{
+    Router, middleware,
+    routing::{get, patch, post, put},
+};
+
+use crate::{
+    handlers::collection::{add_item, create_new_collection, get_collection, get_my_collections, remove_collection, remove_item, reorder_items, update_existing_collection, update_item},
+    middlewares::auth::authorization_middleware,
+};
+
+pub fn collection_routes() -> Router {
+    Router::new()
+        .route("/", get(get_my_collections).post(create_new_collection))
+        .route("/{collection_id}", get(get_collection).patch(update_existing_collection).delete(remove_collection))
+        .route("/{collection_id}/items", post(add_item))
+        .route("/{collection_id}/items/order", put(reorder_items))
+        .route("/{collection_id}/items/{item_id}", patch(update_item).delete(remove_item))
+        .layer(middleware::from_fn(authorization_middleware))
+}
@@ -0,0 +1,23 @@
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post},
+};
+
+use crate::{
+    handlers::exp_event::{create_new_exp_event, remove_exp_event, search_exp_events},
+    middlewares::{admin::admin_middleware, auth::authorization_middleware},
+};
+
+fn exp_event_admin_routes() -> Router {
+    Router::new()
+        .route("/", post(create_new_exp_event))
+        .route("/{event_id}", delete(remove_exp_event))
+        .route_layer(middleware::from_fn(admin_middleware))
+}
+
+pub fn exp_event_routes() -> Router {
+    Router::new()
+        .route("/", get(search_exp_events))
+        .merge(exp_event_admin_routes())
+        .layer(middleware::from_fn(authorization_middleware))
+}
@@ -0,0 +1,7 @@
+use axum::{Router, middleware, routing::get};
+
+use crate::{handlers::leaderboard::get_leaderboard, middlewares::auth::authorization_middleware};
+
+pub fn leaderboard_routes() -> Router {
+    Router::new().route("/{period}", get(get_leaderboard)).layer(middleware::from_fn(authorization_middleware))
+}
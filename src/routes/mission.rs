@@ -1,12 +1,26 @@
-use axum::{Router, middleware, routing::get};
+use axum::{
+    Router, middleware,
+    routing::{get, patch, post, put},
+};
 
 use crate::{
-    handlers::mission::{create_new_mission, search_missions},
-    middlewares::auth::authorization_middleware,
+    handlers::mission::{create_new_mission, disable_mission, enable_mission, get_my_missions, remove_mission, schedule_mission, search_missions, update_existing_mission},
+    middlewares::{admin::admin_middleware, auth::authorization_middleware},
 };
 
+fn mission_admin_routes() -> Router {
+    Router::new()
+        .route("/", post(create_new_mission))
+        .route("/{mission_id}", patch(update_existing_mission).delete(remove_mission))
+        .route("/{mission_id}/schedule", put(schedule_mission))
+        .route("/{mission_id}/disable", put(disable_mission).delete(enable_mission))
+        .route_layer(middleware::from_fn(admin_middleware))
+}
+
 pub fn mission_routes() -> Router {
     Router::new()
-        .route("/", get(search_missions).post(create_new_mission))
+        .route("/", get(search_missions))
+        .route("/me", get(get_my_missions))
+        .merge(mission_admin_routes())
         .layer(middleware::from_fn(authorization_middleware))
 }
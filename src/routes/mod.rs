@@ -1,7 +1,15 @@
+pub mod achievement;
 pub mod auth;
+pub mod category;
+pub mod collection;
+pub mod exp_event;
 pub mod iap;
+pub mod leaderboard;
 pub mod mission;
+pub mod notification;
 pub mod place;
+pub mod place_edit;
+pub mod quest;
 pub mod review;
 pub mod upload;
 pub mod user;
@@ -0,0 +1,7 @@
+use axum::{Router, routing::get};
+
+use crate::handlers::notification::{confirm_unsubscribe, unsubscribe};
+
+pub fn notification_routes() -> Router {
+    Router::new().route("/unsubscribe", get(confirm_unsubscribe).post(unsubscribe))
+}
@@ -1,16 +1,22 @@
 use axum::{
     Router, middleware,
-    routing::{patch, post},
+    routing::{get, patch, post},
 };
 
 use crate::{
-    handlers::place::{increase_view, upsert_place},
+    handlers::place::{get_place_detail, increase_view, search_nearby_places, search_recommended_places, search_trending_places, upsert_place},
+    handlers::place_edit::suggest_place_edit,
     middlewares::auth::authorization_middleware,
 };
 
 pub fn place_routes() -> Router {
     Router::new()
+        .route("/{place_id}", get(get_place_detail))
         .route("/{place_id}/increase-view", patch(increase_view))
+        .route("/{place_id}/suggestions", post(suggest_place_edit))
+        .route("/nearby", get(search_nearby_places))
+        .route("/trending", get(search_trending_places))
+        .route("/recommended", get(search_recommended_places))
         .route("/upsert", post(upsert_place))
         .layer(middleware::from_fn(authorization_middleware))
 }
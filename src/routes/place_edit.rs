@@ -0,0 +1,19 @@
+use axum::{
+    Router, middleware,
+    routing::{get, post},
+};
+
+use crate::{
+    handlers::place_edit::{approve_place_edit, get_place_edit_suggestion, reject_place_edit, search_place_edit_suggestions},
+    middlewares::{admin::admin_middleware, auth::authorization_middleware},
+};
+
+pub fn place_edit_routes() -> Router {
+    Router::new()
+        .route("/", get(search_place_edit_suggestions))
+        .route("/{suggestion_id}", get(get_place_edit_suggestion))
+        .route("/{suggestion_id}/approve", post(approve_place_edit))
+        .route("/{suggestion_id}/reject", post(reject_place_edit))
+        .route_layer(middleware::from_fn(admin_middleware))
+        .layer(middleware::from_fn(authorization_middleware))
+}
@@ -0,0 +1,20 @@
+use axum::{
+    Router, middleware,
+    routing::{get, post},
+};
+
+use crate::{
+    handlers::quest::{create_new_quest, search_quests},
+    middlewares::{admin::admin_middleware, auth::authorization_middleware},
+};
+
+fn quest_admin_routes() -> Router {
+    Router::new().route("/", post(create_new_quest)).route_layer(middleware::from_fn(admin_middleware))
+}
+
+pub fn quest_routes() -> Router {
+    Router::new()
+        .route("/", get(search_quests))
+        .merge(quest_admin_routes())
+        .layer(middleware::from_fn(authorization_middleware))
+}
@@ -1,17 +1,32 @@
 use axum::{
-    Router, middleware,
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
     routing::{get, post, put},
 };
 
 use crate::{
-    handlers::user::{check_in, get_profile, invite, update_photo},
+    handlers::{
+        collection::get_user_collections,
+        user::{
+            check_in, follow, get_my_exp_history, get_my_stats, get_my_visits, get_notification_preferences, get_profile, invite, unfollow, update_notification_preferences, update_photo,
+            update_timezone,
+        },
+    },
     middlewares::auth::authorization_middleware,
 };
 
 pub fn user_routes() -> Router {
     Router::new()
         .route("/{user_id}", get(get_profile))
-        .route("/photo", put(update_photo))
+        .route("/{user_id}/follow", put(follow).delete(unfollow))
+        .route("/{user_id}/collections", get(get_user_collections))
+        .route("/me/exp-history", get(get_my_exp_history))
+        .route("/me/visits", get(get_my_visits))
+        .route("/me/stats", get(get_my_stats))
+        .route("/me/timezone", put(update_timezone))
+        .route("/me/notification-preferences", get(get_notification_preferences).put(update_notification_preferences))
+        .route("/photo/{field}", put(update_photo).layer(DefaultBodyLimit::max(10 * 1024 * 1024)))
         .route("/check-in", get(check_in))
         .route("/invite", post(invite))
         .layer(middleware::from_fn(authorization_middleware))
@@ -1,5 +1,22 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    achievements (id) {
+        id -> Uuid,
+        #[max_length = 30]
+        code -> Varchar,
+        #[max_length = 50]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        icon -> Nullable<Text>,
+        #[max_length = 20]
+        metric -> Varchar,
+        place_type -> Nullable<Varchar>,
+        threshold -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     action_count (id) {
         id -> Uuid,
@@ -9,6 +26,92 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    categories (id) {
+        id -> Uuid,
+        #[max_length = 50]
+        code -> Varchar,
+        #[max_length = 50]
+        name -> Varchar,
+        #[max_length = 50]
+        parent_code -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    category_google_types (google_type) {
+        #[max_length = 100]
+        google_type -> Varchar,
+        #[max_length = 50]
+        category_code -> Varchar,
+        priority -> Int4,
+    }
+}
+
+diesel::table! {
+    check_in_streaks (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        current_streak -> Int4,
+        longest_streak -> Int4,
+        last_check_in_date -> Nullable<Date>,
+        freeze_tokens -> Int4,
+        created_at -> Timestamp,
+        started_on -> Nullable<Date>,
+    }
+}
+
+diesel::table! {
+    collection_items (id) {
+        id -> Uuid,
+        collection_id -> Uuid,
+        place_id -> Uuid,
+        position -> Int4,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    collections (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        #[max_length = 10]
+        visibility -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    email_unsubscribes (id) {
+        id -> Uuid,
+        email -> Varchar,
+        #[max_length = 20]
+        category -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    exp_events (id) {
+        id -> Uuid,
+        #[max_length = 50]
+        name -> Varchar,
+        multiplier -> Float8,
+        #[max_length = 20]
+        mission_code -> Nullable<Varchar>,
+        place_type -> Nullable<Varchar>,
+        starts_at -> Timestamp,
+        ends_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     exp_history (id) {
         id -> Uuid,
@@ -17,6 +120,7 @@ diesel::table! {
         source -> Nullable<Varchar>,
         amount -> Nullable<Int4>,
         created_at -> Timestamp,
+        multiplier -> Float8,
     }
 }
 
@@ -29,6 +133,35 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    follows (id) {
+        id -> Uuid,
+        follower_id -> Uuid,
+        followee_id -> Uuid,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    invites (id) {
+        id -> Uuid,
+        inviter_id -> Uuid,
+        invitee_id -> Nullable<Uuid>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    levels (level) {
+        level -> Int4,
+        exp_required -> Int4,
+        #[max_length = 20]
+        reward_type -> Nullable<Varchar>,
+        reward_count -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     missions (id) {
         id -> Uuid,
@@ -43,6 +176,66 @@ diesel::table! {
         gift_reward_type -> Nullable<Varchar>,
         max_per_day -> Nullable<Int4>,
         created_at -> Timestamp,
+        #[max_length = 10]
+        period -> Varchar,
+        active_from -> Nullable<Timestamp>,
+        active_until -> Nullable<Timestamp>,
+        #[max_length = 30]
+        gift_reward_code -> Nullable<Varchar>,
+        is_disabled -> Bool,
+    }
+}
+
+diesel::table! {
+    notification_preferences (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 20]
+        category -> Varchar,
+        in_app -> Bool,
+        push -> Bool,
+        email -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    place_edit_events (id) {
+        id -> Uuid,
+        suggestion_id -> Uuid,
+        actor_id -> Uuid,
+        #[max_length = 10]
+        action -> Varchar,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    place_edit_suggestions (id) {
+        id -> Uuid,
+        place_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 30]
+        field -> Varchar,
+        proposed_value -> Nullable<Text>,
+        reason -> Nullable<Text>,
+        #[max_length = 10]
+        status -> Varchar,
+        reviewed_by -> Nullable<Uuid>,
+        reviewed_at -> Nullable<Timestamp>,
+        review_note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    place_view_buckets (place_id, bucket_start) {
+        place_id -> Uuid,
+        bucket_start -> Timestamp,
+        view_count -> Int4,
     }
 }
 
@@ -72,6 +265,44 @@ diesel::table! {
         plus_code -> Nullable<Jsonb>,
         created_at -> Timestamp,
         range_time_view_count -> Int4,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+        trending_score -> Float8,
+        updated_at -> Timestamp,
+        opening_hours -> Nullable<Jsonb>,
+        overridden_fields -> Array<Text>,
+    }
+}
+
+diesel::table! {
+    quest_steps (id) {
+        id -> Uuid,
+        quest_id -> Uuid,
+        #[max_length = 10]
+        action -> Varchar,
+        place_type -> Nullable<Varchar>,
+        target_count -> Int4,
+        is_distinct -> Bool,
+        created_at -> Timestamp,
+        position -> Int4,
+    }
+}
+
+diesel::table! {
+    quests (id) {
+        id -> Uuid,
+        #[max_length = 30]
+        code -> Varchar,
+        #[max_length = 50]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        #[max_length = 10]
+        period -> Varchar,
+        #[max_length = 20]
+        mission_code -> Varchar,
+        active_from -> Nullable<Timestamp>,
+        active_until -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
@@ -116,6 +347,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_achievements (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        achievement_id -> Uuid,
+        unlocked_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_quests (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        quest_id -> Uuid,
+        #[max_length = 20]
+        period_key -> Varchar,
+        progress -> Array<Int4>,
+        completed_at -> Nullable<Timestamp>,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     user_place_access (id) {
         id -> Uuid,
@@ -124,6 +377,7 @@ diesel::table! {
         #[sql_name = "type"]
         type_ -> Varchar,
         created_at -> Timestamp,
+        visited_on -> Date,
     }
 }
 
@@ -137,26 +391,65 @@ diesel::table! {
         exp -> Nullable<Int4>,
         avatar_url -> Nullable<Text>,
         cover_url -> Nullable<Text>,
+        quiet_hours_start -> Nullable<Time>,
+        quiet_hours_end -> Nullable<Time>,
+        is_admin -> Bool,
+        #[max_length = 64]
+        timezone -> Varchar,
+        timezone_updated_at -> Nullable<Timestamp>,
     }
 }
 
 diesel::joinable!(action_count -> users (user_id));
+diesel::joinable!(check_in_streaks -> users (user_id));
+diesel::joinable!(collection_items -> collections (collection_id));
+diesel::joinable!(collection_items -> places (place_id));
+diesel::joinable!(collections -> users (user_id));
 diesel::joinable!(exp_history -> users (user_id));
 diesel::joinable!(feature_usages -> users (user_id));
+diesel::joinable!(notification_preferences -> users (user_id));
+diesel::joinable!(place_edit_events -> place_edit_suggestions (suggestion_id));
+diesel::joinable!(place_edit_events -> users (actor_id));
+diesel::joinable!(place_edit_suggestions -> places (place_id));
+diesel::joinable!(place_view_buckets -> places (place_id));
+diesel::joinable!(quest_steps -> quests (quest_id));
 diesel::joinable!(reviews -> places (place_id));
 diesel::joinable!(reviews -> users (user_id));
 diesel::joinable!(subscriptions -> users (user_id));
+diesel::joinable!(user_achievements -> achievements (achievement_id));
+diesel::joinable!(user_achievements -> users (user_id));
 diesel::joinable!(user_place_access -> places (place_id));
 diesel::joinable!(user_place_access -> users (user_id));
+diesel::joinable!(user_quests -> quests (quest_id));
+diesel::joinable!(user_quests -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    achievements,
     action_count,
+    categories,
+    category_google_types,
+    check_in_streaks,
+    collection_items,
+    collections,
+    email_unsubscribes,
+    exp_events,
     exp_history,
     feature_usages,
+    follows,
+    invites,
+    levels,
     missions,
+    notification_preferences,
+    place_edit_events,
+    place_edit_suggestions,
+    place_view_buckets,
     places,
+    quest_steps,
+    quests,
     reviews,
     subscriptions,
+    user_achievements,
     user_place_access,
+    user_quests,
     users,
 );
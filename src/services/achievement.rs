@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::achievement::{Achievement, AchievementEvent, NewUserAchievement, UserAchievement},
+    schema::{achievements, reviews, user_achievements, user_place_access, users},
+    services::{
+        category::{get_categories, get_descendant_codes},
+        invite::count_invites_by_inviter,
+    },
+};
+
+pub async fn get_achievements(conn: &mut DbConn) -> Result<Vec<Achievement>, diesel::result::Error> {
+    achievements::table
+        .order((achievements::metric.asc(), achievements::threshold.asc()))
+        .select(Achievement::as_select())
+        .load(conn)
+        .await
+}
+
+pub async fn get_user_achievements(conn: &mut DbConn, user_id: Uuid) -> Result<Vec<(UserAchievement, Achievement)>, diesel::result::Error> {
+    user_achievements::table
+        .inner_join(achievements::table)
+        .filter(user_achievements::user_id.eq(user_id))
+        .order(user_achievements::unlocked_at.desc())
+        .select((UserAchievement::as_select(), Achievement::as_select()))
+        .load(conn)
+        .await
+}
+
+/// Current value of each achievement's metric for the user, by achievement id, compared against
+/// its `threshold`.
+///
+/// # Behavior
+/// Each metric is queried once whatever the number of achievements using it; `PLACE_VISITS` loads
+/// the user's visited places with their category once and counts them per `place_type` in memory.
+///
+pub async fn get_achievements_progress(conn: &mut DbConn, user_id: Uuid, achievements: &[Achievement]) -> Result<HashMap<Uuid, i64>, diesel::result::Error> {
+    let metrics: HashSet<&str> = achievements.iter().map(|achievement| achievement.metric.as_str()).collect();
+
+    let review_count = if metrics.contains("REVIEW_COUNT") {
+        reviews::table.filter(reviews::user_id.eq(user_id)).count().get_result::<i64>(conn).await?
+    } else {
+        0
+    };
+
+    let level = if metrics.contains("LEVEL") {
+        users::table.filter(users::id.eq(user_id)).select(users::level).first::<Option<i32>>(conn).await?.unwrap_or(0) as i64
+    } else {
+        0
+    };
+
+    let invite_count = if metrics.contains("INVITE_COUNT") { count_invites_by_inviter(conn, user_id).await? } else { 0 };
+
+    let (visits, categories) = if metrics.contains("PLACE_VISITS") {
+        let visits: Vec<(String, Uuid)> = user_place_access::table
+            .filter(user_place_access::user_id.eq(user_id))
+            .select((user_place_access::type_, user_place_access::place_id))
+            .distinct()
+            .load(conn)
+            .await?;
+
+        (visits, get_categories(conn).await?)
+    } else {
+        (vec![], vec![])
+    };
+
+    Ok(achievements
+        .iter()
+        .map(|achievement| {
+            let progress = match achievement.metric.as_str() {
+                "REVIEW_COUNT" => review_count,
+                "PLACE_VISITS" => {
+                    let category_codes = achievement.place_type.as_ref().map(|place_type| get_descendant_codes(&categories, place_type));
+
+                    let place_ids: HashSet<Uuid> = visits
+                        .iter()
+                        .filter(|(code, _)| category_codes.as_ref().is_none_or(|category_codes| category_codes.contains(code)))
+                        .map(|(_, place_id)| *place_id)
+                        .collect();
+
+                    place_ids.len() as i64
+                }
+                "LEVEL" => level,
+                "INVITE_COUNT" => invite_count,
+                _ => 0,
+            };
+
+            (achievement.id, progress)
+        })
+        .collect())
+}
+
+/// Unlocks the achievement with `code` regardless of its metric, e.g. for badges granted as gifts.
+///
+/// # Returns
+/// The achievement if this call unlocked it, `None` if the user already had it.
+///
+pub async fn unlock_achievement_by_code(conn: &mut DbConn, user_id: Uuid, code: &str) -> Result<Option<Achievement>, diesel::result::Error> {
+    let achievement = achievements::table
+        .filter(achievements::code.eq(code))
+        .select(Achievement::as_select())
+        .first::<Achievement>(conn)
+        .await?;
+
+    let new_user_achievement = NewUserAchievement {
+        user_id,
+        achievement_id: achievement.id,
+    };
+
+    let inserted = diesel::insert_into(user_achievements::table)
+        .values(&new_user_achievement)
+        .on_conflict((user_achievements::user_id, user_achievements::achievement_id))
+        .do_nothing()
+        .execute(conn)
+        .await?;
+
+    Ok((inserted > 0).then_some(achievement))
+}
+
+/// Unlocks every achievement whose metric is affected by `event` and whose threshold the user has
+/// reached. Already unlocked achievements are skipped, so it is safe to run on every event.
+///
+/// # Returns
+/// The achievements unlocked by this call.
+///
+pub async fn evaluate_achievements(conn: &mut DbConn, user_id: Uuid, event: AchievementEvent) -> Result<Vec<Achievement>, diesel::result::Error> {
+    let unlocked_ids: Vec<Uuid> = user_achievements::table
+        .filter(user_achievements::user_id.eq(user_id))
+        .select(user_achievements::achievement_id)
+        .load(conn)
+        .await?;
+
+    let candidates: Vec<Achievement> = achievements::table
+        .filter(achievements::metric.eq_any(event.metrics()))
+        .select(Achievement::as_select())
+        .load::<Achievement>(conn)
+        .await?
+        .into_iter()
+        .filter(|achievement| !unlocked_ids.contains(&achievement.id))
+        .collect();
+
+    let progress = get_achievements_progress(conn, user_id, &candidates).await?;
+
+    let mut unlocked = vec![];
+
+    for achievement in candidates {
+        if progress.get(&achievement.id).copied().unwrap_or(0) < achievement.threshold as i64 {
+            continue;
+        }
+
+        let new_user_achievement = NewUserAchievement {
+            user_id,
+            achievement_id: achievement.id,
+        };
+
+        let inserted = diesel::insert_into(user_achievements::table)
+            .values(&new_user_achievement)
+            .on_conflict((user_achievements::user_id, user_achievements::achievement_id))
+            .do_nothing()
+            .execute(conn)
+            .await?;
+
+        if inserted > 0 {
+            unlocked.push(achievement);
+        }
+    }
+
+    Ok(unlocked)
+}
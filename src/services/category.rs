@@ -0,0 +1,132 @@
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    config::db::DbConn,
+    models::category::{Category, CategoryGoogleType, ReturnCategory},
+    schema::{categories, category_google_types},
+};
+
+pub async fn get_categories(conn: &mut DbConn) -> Result<Vec<Category>, diesel::result::Error> {
+    categories::table.order(categories::code.asc()).select(Category::as_select()).load(conn).await
+}
+
+pub async fn get_category_google_types(conn: &mut DbConn) -> Result<Vec<CategoryGoogleType>, diesel::result::Error> {
+    category_google_types::table.select(CategoryGoogleType::as_select()).load(conn).await
+}
+
+/// Children of `parent_code`, or the root categories when `None`, each with its Google types from
+/// `mappings` and its own subtree.
+pub fn build_category_tree(categories: &[Category], mappings: &[CategoryGoogleType], parent_code: Option<&str>) -> Vec<ReturnCategory> {
+    categories
+        .iter()
+        .filter(|category| category.parent_code.as_deref() == parent_code)
+        .map(|category| ReturnCategory {
+            category: category.clone(),
+            google_types: mappings
+                .iter()
+                .filter(|mapping| mapping.category_code == category.code)
+                .map(|mapping| mapping.google_type.clone())
+                .collect(),
+            children: build_category_tree(categories, mappings, Some(&category.code)),
+        })
+        .collect()
+}
+
+/// `code` and the codes of all categories below it.
+pub fn get_descendant_codes(categories: &[Category], code: &str) -> Vec<String> {
+    let mut codes = vec![code.to_string()];
+    let mut index = 0;
+
+    while index < codes.len() {
+        let parent_code = codes[index].clone();
+
+        for category in categories.iter().filter(|category| category.parent_code.as_deref() == Some(parent_code.as_str())) {
+            if !codes.contains(&category.code) {
+                codes.push(category.code.clone());
+            }
+        }
+
+        index += 1;
+    }
+
+    codes
+}
+
+/// `code` and the codes of the categories above it, up to its root category.
+pub fn get_ancestor_codes(categories: &[Category], code: &str) -> Vec<String> {
+    let mut codes = vec![code.to_string()];
+    let mut current = code;
+
+    while let Some(parent_code) = categories.iter().find(|category| category.code == current).and_then(|category| category.parent_code.as_deref()) {
+        if codes.iter().any(|code| code == parent_code) {
+            break;
+        }
+
+        codes.push(parent_code.to_string());
+        current = parent_code;
+    }
+
+    codes
+}
+
+/// Category of a place of Google `types`: the mapping with the highest priority among its types,
+/// the earliest type winning ties, or `None` when no type is mapped.
+pub fn resolve_category(mappings: &[CategoryGoogleType], types: &[String]) -> Option<String> {
+    types
+        .iter()
+        .filter_map(|type_| mappings.iter().find(|mapping| &mapping.google_type == type_))
+        .reduce(|best, mapping| if mapping.priority > best.priority { mapping } else { best })
+        .map(|mapping| mapping.category_code.clone())
+}
+
+/// Category of a place of Google `types`, see `resolve_category`.
+pub async fn get_place_category(conn: &mut DbConn, types: &[String]) -> Result<Option<String>, diesel::result::Error> {
+    let mappings: Vec<CategoryGoogleType> = category_google_types::table
+        .filter(category_google_types::google_type.eq_any(types))
+        .select(CategoryGoogleType::as_select())
+        .load(conn)
+        .await?;
+
+    Ok(resolve_category(&mappings, types))
+}
+
+/// Google `types` of a place followed by its category and the categories above it, so that filters
+/// naming either a Google type or a category match the place.
+pub async fn get_place_type_tags(conn: &mut DbConn, types: &[String]) -> Result<Vec<String>, diesel::result::Error> {
+    let mut tags = types.to_vec();
+
+    if let Some(code) = get_place_category(conn, types).await? {
+        let categories = get_categories(conn).await?;
+
+        tags.extend(get_ancestor_codes(&categories, &code));
+    }
+
+    Ok(tags)
+}
+
+/// Category codes matching `code`: the category and its descendants, or `code` alone when it is
+/// not a category, e.g. a raw Google type.
+pub async fn get_matching_category_codes(conn: &mut DbConn, code: &str) -> Result<Vec<String>, diesel::result::Error> {
+    let categories = get_categories(conn).await?;
+
+    Ok(get_descendant_codes(&categories, code))
+}
+
+/// Google types of the places matching `code`: the types mapped to the category or its
+/// descendants, plus `code` itself so that raw Google types keep matching.
+pub async fn get_matching_google_types(conn: &mut DbConn, code: &str) -> Result<Vec<String>, diesel::result::Error> {
+    let category_codes = get_matching_category_codes(conn, code).await?;
+
+    let mut google_types: Vec<String> = category_google_types::table
+        .filter(category_google_types::category_code.eq_any(&category_codes))
+        .select(category_google_types::google_type)
+        .load(conn)
+        .await?;
+
+    if !google_types.iter().any(|google_type| google_type == code) {
+        google_types.push(code.to_string());
+    }
+
+    Ok(google_types)
+}
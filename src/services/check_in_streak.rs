@@ -0,0 +1,143 @@
+use chrono::{NaiveDate, NaiveTime};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::check_in_streak::{CheckInStreak, MAX_FREEZE_TOKENS, NewCheckInStreak, ReturnCheckInStreak, StreakReward},
+    schema::check_in_streaks,
+    services::{exp_history::has_exp_history_by_source_since, feature_usage::give_usage_count_to_user, user::give_exp_and_level_up},
+};
+
+pub async fn get_check_in_streak_by_user(conn: &mut DbConn, user_id: Uuid) -> Result<Option<CheckInStreak>, diesel::result::Error> {
+    check_in_streaks::table
+        .filter(check_in_streaks::user_id.eq(user_id))
+        .select(CheckInStreak::as_select())
+        .first::<CheckInStreak>(conn)
+        .await
+        .optional()
+}
+
+pub async fn upsert_check_in_streak(conn: &mut DbConn, payload: &NewCheckInStreak) -> Result<CheckInStreak, diesel::result::Error> {
+    diesel::insert_into(check_in_streaks::table)
+        .values(payload)
+        .on_conflict(check_in_streaks::user_id)
+        .do_update()
+        .set(payload)
+        .returning(CheckInStreak::as_returning())
+        .get_result::<CheckInStreak>(conn)
+        .await
+}
+
+/// Rewards for reaching `streak` consecutive check-ins: 3, 7 and 14 days, then every 30 days.
+pub fn milestone_rewards(streak: i32) -> Vec<StreakReward> {
+    match streak {
+        3 => vec![StreakReward::Exp(20)],
+        7 => vec![StreakReward::Exp(50), StreakReward::FreezeToken(1)],
+        14 => vec![StreakReward::Exp(100)],
+        streak if streak > 0 && streak % 30 == 0 => vec![StreakReward::Exp(200), StreakReward::RouteCalculation(3), StreakReward::FreezeToken(1)],
+        _ => vec![],
+    }
+}
+
+/// Computes the streak after checking in on `today`.
+///
+/// # Behavior
+/// - Checking in the day after the last check-in extends the streak.
+/// - Missed days are covered by freeze tokens when the user holds enough of them; otherwise the
+///   streak restarts at 1.
+/// - A repeated check-in on the same day leaves the streak unchanged, as does one dated before the
+///   last check-in, e.g. after a timezone change.
+///
+pub fn advance_streak(user_id: Uuid, streak: Option<&CheckInStreak>, today: NaiveDate) -> NewCheckInStreak {
+    let (current_streak, longest_streak, last_check_in_date, freeze_tokens, started_on) = match streak {
+        Some(streak) => (streak.current_streak, streak.longest_streak, streak.last_check_in_date, streak.freeze_tokens, streak.started_on),
+        None => (0, 0, None, 0, None),
+    };
+
+    let (current_streak, freeze_tokens, started_on) = match last_check_in_date.map(|date| (today - date).num_days()) {
+        Some(days) if days <= 0 => (current_streak, freeze_tokens, started_on),
+        Some(days) if days > 0 && (days - 1) as i32 <= freeze_tokens => (current_streak + 1, freeze_tokens - (days - 1) as i32, started_on),
+        _ => (1, freeze_tokens, Some(today)),
+    };
+
+    NewCheckInStreak {
+        user_id,
+        current_streak,
+        longest_streak: longest_streak.max(current_streak),
+        last_check_in_date: last_check_in_date.max(Some(today)),
+        freeze_tokens,
+        started_on,
+    }
+}
+
+/// Streak as shown to clients on `today`: a streak whose missed days cannot be covered by freeze
+/// tokens anymore is reported as 0.
+pub fn get_return_check_in_streak(streak: Option<&CheckInStreak>, today: NaiveDate) -> ReturnCheckInStreak {
+    match streak {
+        Some(streak) => {
+            let is_broken = streak.last_check_in_date.is_none_or(|date| (today - date).num_days() - 1 > streak.freeze_tokens as i64);
+
+            ReturnCheckInStreak {
+                current_streak: if is_broken { 0 } else { streak.current_streak },
+                longest_streak: streak.longest_streak,
+                last_check_in_date: streak.last_check_in_date,
+                freeze_tokens: streak.freeze_tokens,
+            }
+        }
+        None => ReturnCheckInStreak {
+            current_streak: 0,
+            longest_streak: 0,
+            last_check_in_date: None,
+            freeze_tokens: 0,
+        },
+    }
+}
+
+/// Records a check-in on `today` and grants the milestone rewards it reaches, in one transaction.
+///
+/// # Returns
+/// The updated streak and the rewards granted.
+///
+/// # Behavior
+/// Milestones are only rewarded when the check-in extends the streak, and at most once per streak:
+/// a milestone whose EXP was already granted since the streak started is skipped.
+///
+pub async fn record_check_in(conn: &mut DbConn, user_id: Uuid, today: NaiveDate) -> Result<(CheckInStreak, Vec<StreakReward>), diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let current = get_check_in_streak_by_user(conn, user_id).await?;
+
+            let previous_streak = current.as_ref().map_or(0, |streak| streak.current_streak);
+
+            let mut next = advance_streak(user_id, current.as_ref(), today);
+
+            let source = format!("STREAK_{}", next.current_streak);
+
+            let mut rewards = if next.current_streak > previous_streak { milestone_rewards(next.current_streak) } else { vec![] };
+
+            if !rewards.is_empty() && has_exp_history_by_source_since(conn, user_id, &source, next.started_on.map(|date| date.and_time(NaiveTime::MIN))).await? {
+                rewards.clear();
+            }
+
+            let user_id_string = user_id.to_string();
+
+            for reward in &rewards {
+                match reward {
+                    StreakReward::Exp(amount) => {
+                        give_exp_and_level_up(conn, &user_id_string, *amount, &source, 1.0).await?;
+                    }
+                    StreakReward::RouteCalculation(count) => give_usage_count_to_user(conn, &user_id_string, *count).await?,
+                    StreakReward::FreezeToken(count) => next.freeze_tokens = (next.freeze_tokens + count).min(MAX_FREEZE_TOKENS),
+                }
+            }
+
+            let streak = upsert_check_in_streak(conn, &next).await?;
+
+            Ok((streak, rewards))
+        }
+        .scope_boxed()
+    })
+    .await
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::{
+        collection::{Collection, CollectionItem, CollectionVisibility, NewCollection, NewCollectionItem, UpdateCollection},
+        place::Place,
+    },
+    schema::{collection_items, collections, places},
+};
+
+pub async fn create_collection(conn: &mut DbConn, payload: &NewCollection) -> Result<Collection, diesel::result::Error> {
+    diesel::insert_into(collections::table)
+        .values(payload)
+        .returning(Collection::as_returning())
+        .get_result::<Collection>(conn)
+        .await
+}
+
+pub async fn get_collection_by_id(conn: &mut DbConn, id: Uuid) -> Result<Collection, diesel::result::Error> {
+    collections::table.filter(collections::id.eq(id)).select(Collection::as_select()).first::<Collection>(conn).await
+}
+
+/// Collections of the user `user_id`, most recently updated first; only the public ones when
+/// `public_only`.
+pub async fn get_collections_by_user(conn: &mut DbConn, user_id: Uuid, public_only: bool) -> Result<Vec<Collection>, diesel::result::Error> {
+    let mut query = collections::table.filter(collections::user_id.eq(user_id)).into_boxed();
+
+    if public_only {
+        query = query.filter(collections::visibility.eq(CollectionVisibility::Public.as_str()));
+    }
+
+    query.order(collections::updated_at.desc()).select(Collection::as_select()).load(conn).await
+}
+
+pub async fn update_collection(conn: &mut DbConn, id: Uuid, changeset: &UpdateCollection) -> Result<Collection, diesel::result::Error> {
+    diesel::update(collections::table.filter(collections::id.eq(id)))
+        .set(changeset)
+        .returning(Collection::as_returning())
+        .get_result::<Collection>(conn)
+        .await
+}
+
+pub async fn delete_collection(conn: &mut DbConn, id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(collections::table.filter(collections::id.eq(id))).execute(conn).await
+}
+
+/// Items of the collection `collection_id` with their place, in collection order.
+pub async fn get_collection_items(conn: &mut DbConn, collection_id: Uuid) -> Result<Vec<(CollectionItem, Place)>, diesel::result::Error> {
+    collection_items::table
+        .inner_join(places::table)
+        .filter(collection_items::collection_id.eq(collection_id))
+        .order(collection_items::position.asc())
+        .select((CollectionItem::as_select(), Place::as_select()))
+        .load(conn)
+        .await
+}
+
+/// Appends the place `place_id` at the end of the collection `collection_id`.
+///
+/// # Behavior
+/// The collection row is locked while the next position is computed, so concurrent additions get
+/// distinct positions. Adding a place already in the collection fails with a unique violation.
+///
+pub async fn add_collection_item(conn: &mut DbConn, collection_id: Uuid, place_id: Uuid, note: Option<String>) -> Result<CollectionItem, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            diesel::QueryDsl::for_update(collections::table.filter(collections::id.eq(collection_id)))
+                .select(collections::id)
+                .first::<Uuid>(conn)
+                .await?;
+
+            let last_position: Option<i32> = collection_items::table
+                .filter(collection_items::collection_id.eq(collection_id))
+                .select(diesel::dsl::max(collection_items::position))
+                .first::<Option<i32>>(conn)
+                .await?;
+
+            let new_item = NewCollectionItem {
+                collection_id,
+                place_id,
+                position: last_position.map_or(0, |position| position + 1),
+                note,
+            };
+
+            diesel::insert_into(collection_items::table)
+                .values(&new_item)
+                .returning(CollectionItem::as_returning())
+                .get_result::<CollectionItem>(conn)
+                .await
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+pub async fn update_collection_item_note(conn: &mut DbConn, collection_id: Uuid, item_id: Uuid, note: Option<String>) -> Result<Option<CollectionItem>, diesel::result::Error> {
+    diesel::update(
+        collection_items::table
+            .filter(collection_items::id.eq(item_id))
+            .filter(collection_items::collection_id.eq(collection_id)),
+    )
+    .set(collection_items::note.eq(note))
+    .returning(CollectionItem::as_returning())
+    .get_result::<CollectionItem>(conn)
+    .await
+    .optional()
+}
+
+pub async fn delete_collection_item(conn: &mut DbConn, collection_id: Uuid, item_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(
+        collection_items::table
+            .filter(collection_items::id.eq(item_id))
+            .filter(collection_items::collection_id.eq(collection_id)),
+    )
+    .execute(conn)
+    .await
+}
+
+/// Reorders the items of the collection `collection_id` to follow `item_ids`.
+///
+/// # Behavior
+/// Fails with `RollbackTransaction` unless `item_ids` lists every item of the collection exactly
+/// once.
+///
+pub async fn reorder_collection_items(conn: &mut DbConn, collection_id: Uuid, item_ids: &[Uuid]) -> Result<(), diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let current_ids: HashSet<Uuid> = diesel::QueryDsl::for_update(collection_items::table.filter(collection_items::collection_id.eq(collection_id)))
+                .select(collection_items::id)
+                .load::<Uuid>(conn)
+                .await?
+                .into_iter()
+                .collect();
+
+            let requested_ids: HashSet<Uuid> = item_ids.iter().copied().collect();
+
+            if requested_ids.len() != item_ids.len() || requested_ids != current_ids {
+                return Err(diesel::result::Error::RollbackTransaction);
+            }
+
+            for (position, item_id) in item_ids.iter().enumerate() {
+                diesel::update(collection_items::table.filter(collection_items::id.eq(item_id)))
+                    .set(collection_items::position.eq(position as i32))
+                    .execute(conn)
+                    .await?;
+            }
+
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Coordinates as `[latitude, longitude]` of the places of the collection `collection_id`, in
+/// collection order; places without coordinates are skipped.
+pub async fn get_collection_coordinates(conn: &mut DbConn, collection_id: Uuid) -> Result<Vec<[f64; 2]>, diesel::result::Error> {
+    let coordinates: Vec<(Option<f64>, Option<f64>)> = collection_items::table
+        .inner_join(places::table)
+        .filter(collection_items::collection_id.eq(collection_id))
+        .order(collection_items::position.asc())
+        .select((places::latitude, places::longitude))
+        .load(conn)
+        .await?;
+
+    Ok(coordinates.into_iter().filter_map(|(lat, lng)| Some([lat?, lng?])).collect())
+}
@@ -0,0 +1,30 @@
+use diesel::{ExpressionMethods, OptionalExtension, query_dsl::methods::FilterDsl};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    config::db::DbConn,
+    models::email_unsubscribe::{EmailUnsubscribe, NewEmailUnsubscribe},
+    schema::email_unsubscribes,
+};
+
+pub async fn create_email_unsubscribe(conn: &mut DbConn, payload: &NewEmailUnsubscribe) -> Result<(), diesel::result::Error> {
+    diesel::insert_into(email_unsubscribes::table)
+        .values(payload)
+        .on_conflict((email_unsubscribes::email, email_unsubscribes::category))
+        .do_nothing()
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn is_email_unsubscribed(conn: &mut DbConn, email: &str, category: &str) -> Result<bool, diesel::result::Error> {
+    let unsubscribe = email_unsubscribes::table
+        .filter(email_unsubscribes::email.eq(email))
+        .filter(email_unsubscribes::category.eq(category))
+        .first::<EmailUnsubscribe>(conn)
+        .await
+        .optional()?;
+
+    Ok(unsubscribe.is_some())
+}
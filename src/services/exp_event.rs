@@ -0,0 +1,39 @@
+use chrono::NaiveDateTime;
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::exp_event::{ExpEvent, NewExpEvent},
+    schema::exp_events,
+};
+
+pub async fn create_exp_event(conn: &mut DbConn, payload: &NewExpEvent) -> Result<ExpEvent, diesel::result::Error> {
+    diesel::insert_into(exp_events::table)
+        .values(payload)
+        .returning(ExpEvent::as_returning())
+        .get_result::<ExpEvent>(conn)
+        .await
+}
+
+pub async fn delete_exp_event(conn: &mut DbConn, id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(exp_events::table.filter(exp_events::id.eq(id))).execute(conn).await
+}
+
+/// Events running at `now`, plus the upcoming ones when `include_upcoming` is set.
+pub async fn get_exp_events(conn: &mut DbConn, now: NaiveDateTime, include_upcoming: bool) -> Result<Vec<ExpEvent>, diesel::result::Error> {
+    let mut query = exp_events::table.filter(exp_events::ends_at.gt(now)).into_boxed();
+
+    if !include_upcoming {
+        query = query.filter(exp_events::starts_at.le(now));
+    }
+
+    query.order(exp_events::starts_at.asc()).select(ExpEvent::as_select()).load(conn).await
+}
+
+/// Combined multiplier of the `events` boosting mission `code` on a place tagged `place_types`;
+/// overlapping events stack multiplicatively.
+pub fn get_exp_multiplier(events: &[ExpEvent], code: &str, place_types: &[String]) -> f64 {
+    events.iter().filter(|event| event.applies_to(code, place_types)).map(|event| event.multiplier).product()
+}
@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, dsl::exists};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::exp_history::{ExpHistory, NewExpHistory},
+    schema::exp_history,
+};
+
+pub async fn create_exp_history(conn: &mut DbConn, payload: &NewExpHistory) -> Result<ExpHistory, diesel::result::Error> {
+    diesel::insert_into(exp_history::table)
+        .values(payload)
+        .returning(ExpHistory::as_returning())
+        .get_result::<ExpHistory>(conn)
+        .await
+}
+
+pub async fn get_exp_history_by_user(conn: &mut DbConn, user_id: Uuid, limit: i64, offset: i64) -> Result<(Vec<ExpHistory>, i64), diesel::result::Error> {
+    let total = exp_history::table.filter(exp_history::user_id.eq(user_id)).count().get_result::<i64>(conn).await?;
+
+    let history = exp_history::table
+        .filter(exp_history::user_id.eq(user_id))
+        .order(exp_history::created_at.desc())
+        .limit(limit)
+        .offset(offset)
+        .select(ExpHistory::as_select())
+        .load(conn)
+        .await?;
+
+    Ok((history, total))
+}
+
+pub async fn count_exp_history_by_source(conn: &mut DbConn, user_id: Uuid, source: &str) -> Result<i64, diesel::result::Error> {
+    exp_history::table
+        .filter(exp_history::user_id.eq(user_id))
+        .filter(exp_history::source.eq(source))
+        .count()
+        .get_result::<i64>(conn)
+        .await
+}
+
+/// Whether EXP was ever granted by `source`, for any user.
+pub async fn has_exp_history_by_source(conn: &mut DbConn, source: &str) -> Result<bool, diesel::result::Error> {
+    diesel::select(exists(exp_history::table.filter(exp_history::source.eq(source)))).get_result::<bool>(conn).await
+}
+
+/// Whether the user `user_id` was granted EXP by `source` since `since`, or ever when `None`.
+pub async fn has_exp_history_by_source_since(conn: &mut DbConn, user_id: Uuid, source: &str, since: Option<NaiveDateTime>) -> Result<bool, diesel::result::Error> {
+    let mut query = exp_history::table.filter(exp_history::user_id.eq(user_id)).filter(exp_history::source.eq(source)).into_boxed();
+
+    if let Some(since) = since {
+        query = query.filter(exp_history::created_at.ge(since));
+    }
+
+    diesel::select(exists(query)).get_result::<bool>(conn).await
+}
+
+/// Total EXP earned per user since `since`, or over all time when `None`.
+pub async fn sum_exp_history_by_user(conn: &mut DbConn, since: Option<NaiveDateTime>) -> Result<Vec<(Uuid, i64)>, diesel::result::Error> {
+    let mut query = exp_history::table
+        .filter(exp_history::user_id.is_not_null())
+        .group_by(exp_history::user_id)
+        .select((exp_history::user_id, diesel::dsl::sum(exp_history::amount)))
+        .into_boxed();
+
+    if let Some(since) = since {
+        query = query.filter(exp_history::created_at.ge(since));
+    }
+
+    let totals: Vec<(Option<Uuid>, Option<i64>)> = query.load(conn).await?;
+
+    Ok(totals.into_iter().filter_map(|(user_id, total)| Some((user_id?, total.unwrap_or(0)))).collect())
+}
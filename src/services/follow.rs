@@ -0,0 +1,38 @@
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::follow::{Follow, NewFollow},
+    schema::follows,
+};
+
+/// Follows a user; following someone already followed returns the existing follow.
+pub async fn follow_user(conn: &mut DbConn, payload: &NewFollow) -> Result<Follow, diesel::result::Error> {
+    diesel::insert_into(follows::table)
+        .values(payload)
+        .on_conflict((follows::follower_id, follows::followee_id))
+        .do_nothing()
+        .execute(conn)
+        .await?;
+
+    follows::table
+        .filter(follows::follower_id.eq(payload.follower_id))
+        .filter(follows::followee_id.eq(payload.followee_id))
+        .select(Follow::as_select())
+        .first::<Follow>(conn)
+        .await
+}
+
+pub async fn unfollow_user(conn: &mut DbConn, follower_id: Uuid, followee_id: Uuid) -> Result<(), diesel::result::Error> {
+    diesel::delete(follows::table.filter(follows::follower_id.eq(follower_id)).filter(follows::followee_id.eq(followee_id)))
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_followee_ids(conn: &mut DbConn, follower_id: Uuid) -> Result<Vec<Uuid>, diesel::result::Error> {
+    follows::table.filter(follows::follower_id.eq(follower_id)).select(follows::followee_id).load(conn).await
+}
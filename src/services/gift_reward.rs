@@ -0,0 +1,16 @@
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::gift_reward::GiftReward,
+    services::{achievement::unlock_achievement_by_code, feature_usage::give_usage_count_to_user, subscription::extend_gift_subscription},
+};
+
+/// Grants `reward` to the user; `source` identifies what granted it, e.g. a mission code.
+pub async fn grant_gift_reward(conn: &mut DbConn, user_id: Uuid, reward: &GiftReward, source: &str) -> Result<(), diesel::result::Error> {
+    match reward {
+        GiftReward::RouteCalculation(count) => give_usage_count_to_user(conn, &user_id.to_string(), *count).await,
+        GiftReward::PremiumDays(days) => extend_gift_subscription(conn, user_id, *days, source).await.map(|_| ()),
+        GiftReward::Badge(code) => unlock_achievement_by_code(conn, user_id, code).await.map(|_| ()),
+    }
+}
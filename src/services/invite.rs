@@ -0,0 +1,18 @@
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::invite::{Invite, NewInvite},
+    schema::invites,
+};
+
+pub async fn create_invite(conn: &mut DbConn, payload: &NewInvite) -> Result<Invite, diesel::result::Error> {
+    diesel::insert_into(invites::table).values(payload).returning(Invite::as_returning()).get_result::<Invite>(conn).await
+}
+
+/// Number of users who signed up through an invite of `inviter_id`.
+pub async fn count_invites_by_inviter(conn: &mut DbConn, inviter_id: Uuid) -> Result<i64, diesel::result::Error> {
+    invites::table.filter(invites::inviter_id.eq(inviter_id)).count().get_result::<i64>(conn).await
+}
@@ -0,0 +1,294 @@
+use std::time::Duration;
+
+use redis::{AsyncCommands, ExistenceCheck, Script, SetExpiry, SetOptions};
+use uuid::Uuid;
+
+use crate::{
+    config::{cache::CacheConn, db::DbConn},
+    models::leaderboard::{LeaderboardEntry, LeaderboardPeriod},
+    services::{exp_history::sum_exp_history_by_user, user::get_users_by_ids},
+    utils::time::{DEFAULT_TIMEZONE, get_seconds_to_next_month, get_seconds_to_next_week, get_start_of_month, get_start_of_week, get_this_month, get_this_week},
+};
+
+/// Seconds a rebuild holds the lock of a board before another one may take over.
+const REBUILD_LOCK_SECONDS: u64 = 30;
+
+/// Snapshots taken by a rebuild before giving up on publishing one, when EXP keeps being granted
+/// while the snapshots are taken.
+const REBUILD_ATTEMPTS: usize = 3;
+
+/// Polls for a board being rebuilt by another request, and the delay between them.
+const REBUILD_WAIT_ATTEMPTS: usize = 20;
+const REBUILD_WAIT_MILLIS: u64 = 100;
+
+/// Seconds an unpublished snapshot stays readable.
+const SNAPSHOT_SECONDS: i64 = 60;
+
+/// Adds `ARGV[2]` EXP to the member `ARGV[1]` of the board `KEYS[1]` if it exists, refreshing its
+/// expiry to `ARGV[3]` seconds when positive. While the board is being rebuilt, i.e. its lock
+/// `KEYS[2]` is held, the grant is counted in `KEYS[3]` instead so that the rebuild retries.
+const RECORD_EXP_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[2]) == 1 then
+  redis.call('INCR', KEYS[3])
+  return 0
+end
+if redis.call('EXISTS', KEYS[1]) == 0 then
+  return 0
+end
+redis.call('ZINCRBY', KEYS[1], ARGV[2], ARGV[1])
+if tonumber(ARGV[3]) > 0 then
+  redis.call('EXPIRE', KEYS[1], ARGV[3])
+end
+return 1
+";
+
+/// Renames the snapshot `KEYS[1]` to the board `KEYS[2]`, expiring after `ARGV[2]` seconds when
+/// positive, and releases the lock `KEYS[3]`, provided the rebuild `ARGV[1]` still holds it and no
+/// grant was counted in `KEYS[4]` since the snapshot was taken.
+const PUBLISH_SNAPSHOT_SCRIPT: &str = r"
+if redis.call('GET', KEYS[3]) ~= ARGV[1] or redis.call('GET', KEYS[4]) ~= '0' then
+  return 0
+end
+redis.call('RENAME', KEYS[1], KEYS[2])
+if tonumber(ARGV[2]) > 0 then
+  redis.call('EXPIRE', KEYS[2], ARGV[2])
+end
+redis.call('DEL', KEYS[3], KEYS[4])
+return 1
+";
+
+/// Releases the lock `KEYS[1]` and its grant count `KEYS[2]` if the rebuild `ARGV[1]` still holds it.
+const RELEASE_LOCK_SCRIPT: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+  return redis.call('DEL', KEYS[1], KEYS[2])
+end
+return 0
+";
+
+/// Cache key of the board for the current period and its expiry in seconds, `None` for the global
+/// board which never expires.
+///
+/// # Caching
+/// Boards are sorted sets keyed `leaderboard:global`, `leaderboard:weekly:{YYYY-Www}` and
+/// `leaderboard:monthly:{YYYY-MM}`, with user ids as members and EXP earned as scores. Boards are
+/// shared by every user, so their periods follow `DEFAULT_TIMEZONE`.
+///
+fn get_leaderboard_key(period: LeaderboardPeriod) -> (String, Option<i64>) {
+    match period {
+        LeaderboardPeriod::Global => ("leaderboard:global".to_string(), None),
+        LeaderboardPeriod::Weekly => (format!("leaderboard:weekly:{}", get_this_week(DEFAULT_TIMEZONE)), Some(get_seconds_to_next_week(DEFAULT_TIMEZONE))),
+        LeaderboardPeriod::Monthly => (format!("leaderboard:monthly:{}", get_this_month(DEFAULT_TIMEZONE)), Some(get_seconds_to_next_month(DEFAULT_TIMEZONE))),
+    }
+}
+
+/// Keys of the rebuild lock of the board `key` and of the count of grants recorded while it is held.
+fn get_rebuild_keys(key: &str) -> (String, String) {
+    (format!("{}:rebuild:lock", key), format!("{}:rebuild:grants", key))
+}
+
+/// Adds EXP earned by the user to every board, so this should be called after the grant has been
+/// committed.
+///
+/// # Behavior
+/// - Boards missing from the cache are left alone rather than recreated with this user only; the
+///   next read rebuilds them from `exp_history` through `ensure_leaderboard`, including this grant.
+/// - Boards being rebuilt are left alone too, and the grant is counted so that the rebuild takes a
+///   new snapshot including it.
+///
+pub async fn record_exp_on_leaderboards<'a>(cache_conn: &mut CacheConn<'a>, user_id: &str, exp: i32) -> Result<(), String> {
+    for period in [LeaderboardPeriod::Global, LeaderboardPeriod::Weekly, LeaderboardPeriod::Monthly] {
+        let (key, expire_time) = get_leaderboard_key(period);
+
+        let (lock_key, grants_key) = get_rebuild_keys(&key);
+
+        let _: i32 = Script::new(RECORD_EXP_SCRIPT)
+            .key(&key)
+            .key(&lock_key)
+            .key(&grants_key)
+            .arg(user_id)
+            .arg(exp)
+            .arg(expire_time.unwrap_or(0))
+            .invoke_async(&mut **cache_conn)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Writes the totals of the board `key` for `period` from the `exp_history` ledger to a new sorted
+/// set, expiring after `SNAPSHOT_SECONDS`.
+///
+/// # Returns
+/// The key of the snapshot, `None` if no EXP was earned in the period.
+///
+async fn create_leaderboard_snapshot<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, key: &str, period: LeaderboardPeriod) -> Result<Option<String>, String> {
+    let since = match period {
+        LeaderboardPeriod::Global => None,
+        LeaderboardPeriod::Weekly => Some(get_start_of_week(DEFAULT_TIMEZONE)),
+        LeaderboardPeriod::Monthly => Some(get_start_of_month(DEFAULT_TIMEZONE)),
+    };
+
+    let totals = sum_exp_history_by_user(conn, since).await.map_err(|err| err.to_string())?;
+
+    let items: Vec<(i64, String)> = totals.into_iter().map(|(user_id, total)| (total, user_id.to_string())).collect();
+
+    if items.is_empty() {
+        return Ok(None);
+    }
+
+    let snapshot_key = format!("{}:rebuild:{}", key, Uuid::new_v4());
+
+    let _: usize = cache_conn.zadd_multiple(&snapshot_key, &items).await.map_err(|err| err.to_string())?;
+    let _: i64 = cache_conn.expire(&snapshot_key, SNAPSHOT_SECONDS).await.map_err(|err| err.to_string())?;
+
+    Ok(Some(snapshot_key))
+}
+
+/// Publishes a snapshot of the ledger as the board `key` while the rebuild `rebuild_id` holds its
+/// lock, taking a new snapshot whenever EXP was granted while the previous one was taken.
+///
+/// # Returns
+/// The key to read the board from: the board itself once published, otherwise the last snapshot.
+///
+async fn rebuild_leaderboard<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, period: LeaderboardPeriod, key: &str, expire_time: Option<i64>, rebuild_id: &str) -> Result<String, String> {
+    let (lock_key, grants_key) = get_rebuild_keys(key);
+
+    let mut snapshot_key = None;
+
+    for _ in 0..REBUILD_ATTEMPTS {
+        let _: () = cache_conn.set_ex(&grants_key, 0, REBUILD_LOCK_SECONDS).await.map_err(|err| err.to_string())?;
+
+        let Some(new_snapshot_key) = create_leaderboard_snapshot(conn, cache_conn, key, period).await? else {
+            return Ok(key.to_string());
+        };
+
+        let published: i32 = Script::new(PUBLISH_SNAPSHOT_SCRIPT)
+            .key(&new_snapshot_key)
+            .key(key)
+            .key(&lock_key)
+            .key(&grants_key)
+            .arg(rebuild_id)
+            .arg(expire_time.unwrap_or(0))
+            .invoke_async(&mut **cache_conn)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        if published == 1 {
+            return Ok(key.to_string());
+        }
+
+        snapshot_key = Some(new_snapshot_key);
+    }
+
+    Ok(snapshot_key.unwrap_or_else(|| key.to_string()))
+}
+
+/// Returns the key to read the board for `period` from, rebuilding the board from the `exp_history`
+/// ledger first if it is missing from the cache.
+///
+/// # Behavior
+/// - Only one request rebuilds a board at a time, holding a `SET NX EX` lock; each rebuild writes
+///   its snapshot to its own key before renaming it to the board.
+/// - EXP granted while a snapshot is taken may be missing from it, so the snapshot is only
+///   published if no EXP was granted meanwhile, otherwise a new one is taken. After
+///   `REBUILD_ATTEMPTS` snapshots, the last one is read instead and the board stays missing for
+///   the next read to rebuild.
+/// - Other requests wait for the board, and read a snapshot of their own if it takes too long.
+///
+pub async fn ensure_leaderboard<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, period: LeaderboardPeriod) -> Result<String, String> {
+    let (key, expire_time) = get_leaderboard_key(period);
+
+    let exists: bool = cache_conn.exists(&key).await.map_err(|err| err.to_string())?;
+
+    if exists {
+        return Ok(key);
+    }
+
+    let (lock_key, grants_key) = get_rebuild_keys(&key);
+
+    let rebuild_id = Uuid::new_v4().to_string();
+
+    let lock_options = SetOptions::default().conditional_set(ExistenceCheck::NX).with_expiration(SetExpiry::EX(REBUILD_LOCK_SECONDS));
+
+    let locked: Option<String> = cache_conn.set_options(&lock_key, &rebuild_id, lock_options).await.map_err(|err| err.to_string())?;
+
+    if locked.is_none() {
+        for _ in 0..REBUILD_WAIT_ATTEMPTS {
+            tokio::time::sleep(Duration::from_millis(REBUILD_WAIT_MILLIS)).await;
+
+            let exists: bool = cache_conn.exists(&key).await.map_err(|err| err.to_string())?;
+
+            if exists {
+                return Ok(key);
+            }
+        }
+
+        return Ok(create_leaderboard_snapshot(conn, cache_conn, &key, period).await?.unwrap_or(key));
+    }
+
+    let result = rebuild_leaderboard(conn, cache_conn, period, &key, expire_time, &rebuild_id).await;
+
+    let released: Result<i32, _> = Script::new(RELEASE_LOCK_SCRIPT).key(&lock_key).key(&grants_key).arg(&rebuild_id).invoke_async(&mut **cache_conn).await;
+
+    if let Err(err) = released {
+        eprintln!("Failed to release leaderboard rebuild lock: {}", err);
+    }
+
+    result
+}
+
+/// Attaches user details to `(rank, user_id, exp)` rows.
+pub async fn to_leaderboard_entries(conn: &mut DbConn, rows: Vec<(usize, Uuid, i64)>) -> Result<Vec<LeaderboardEntry>, String> {
+    let ids: Vec<Uuid> = rows.iter().map(|(_, user_id, _)| *user_id).collect();
+
+    let users = get_users_by_ids(conn, &ids).await.map_err(|err| err.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(rank, user_id, exp)| {
+            let user = users.iter().find(|user| user.id == user_id);
+
+            LeaderboardEntry {
+                rank,
+                user_id,
+                exp,
+                level: user.and_then(|user| user.level),
+                avatar_url: user.and_then(|user| user.avatar_url.clone()),
+            }
+        })
+        .collect())
+}
+
+/// Rows ranked `start..=stop` (0-based) of the board, as `(rank, user_id, exp)` with 1-based ranks.
+pub async fn get_leaderboard_range<'a>(cache_conn: &mut CacheConn<'a>, key: &str, start: isize, stop: isize) -> Result<Vec<(usize, Uuid, i64)>, String> {
+    let members: Vec<(String, f64)> = cache_conn.zrevrange_withscores(key, start, stop).await.map_err(|err| err.to_string())?;
+
+    Ok(members
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, (member, score))| Some((start as usize + index + 1, Uuid::parse_str(&member).ok()?, score as i64)))
+        .collect())
+}
+
+/// The user's 0-based rank on the board, `None` if they have not earned EXP in the period.
+pub async fn get_leaderboard_rank<'a>(cache_conn: &mut CacheConn<'a>, key: &str, user_id: Uuid) -> Result<Option<usize>, String> {
+    cache_conn.zrevrank(key, user_id.to_string()).await.map_err(|err| err.to_string())
+}
+
+/// Ranks the given users against each other by their score on the board.
+pub async fn get_leaderboard_among<'a>(cache_conn: &mut CacheConn<'a>, key: &str, user_ids: &[Uuid]) -> Result<Vec<(usize, Uuid, i64)>, String> {
+    if user_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let members: Vec<String> = user_ids.iter().map(Uuid::to_string).collect();
+
+    let scores: Vec<Option<f64>> = cache_conn.zscore_multiple(key, &members).await.map_err(|err| err.to_string())?;
+
+    let mut scores: Vec<(Uuid, i64)> = user_ids.iter().zip(scores).map(|(user_id, score)| (*user_id, score.unwrap_or(0.0) as i64)).collect();
+
+    scores.sort_by_key(|(_, exp)| std::cmp::Reverse(*exp));
+
+    Ok(scores.into_iter().enumerate().map(|(index, (user_id, exp))| (index + 1, user_id, exp)).collect())
+}
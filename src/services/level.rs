@@ -0,0 +1,61 @@
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    config::db::DbConn,
+    models::level::{DEFAULT_EXP_REQUIRED, Level, LevelProgress, LevelUp},
+    schema::levels,
+};
+
+pub async fn get_levels(conn: &mut DbConn) -> Result<Vec<Level>, diesel::result::Error> {
+    levels::table.order(levels::level.asc()).select(Level::as_select()).load(conn).await
+}
+
+/// EXP needed to advance from `level` to the next one. Levels past the end of the curve reuse its
+/// last threshold.
+pub fn exp_required_for(levels: &[Level], level: i32) -> i32 {
+    levels
+        .iter()
+        .find(|item| item.level == level)
+        .or_else(|| levels.iter().filter(|item| item.level <= level).max_by_key(|item| item.level))
+        .map(|item| item.exp_required)
+        .filter(|exp_required| *exp_required > 0)
+        .unwrap_or(DEFAULT_EXP_REQUIRED)
+}
+
+/// Consumes `exp` level by level along the curve.
+///
+/// # Returns
+/// The resulting level, the EXP left towards the next level and every level reached on the way.
+///
+pub fn resolve_level_up(levels: &[Level], level: i32, exp: i32) -> LevelUp {
+    let mut level = level;
+    let mut exp = exp;
+    let mut reached = vec![];
+
+    loop {
+        let exp_required = exp_required_for(levels, level);
+
+        if exp < exp_required {
+            break;
+        }
+
+        exp -= exp_required;
+        level += 1;
+        reached.push(level);
+    }
+
+    LevelUp { level, exp, reached }
+}
+
+pub fn level_progress(levels: &[Level], level: i32, exp: i32) -> LevelProgress {
+    let exp_required = exp_required_for(levels, level);
+
+    LevelProgress {
+        level,
+        exp,
+        exp_required,
+        exp_to_next_level: (exp_required - exp).max(0),
+        progress: (exp as f64 / exp_required as f64).clamp(0.0, 1.0),
+    }
+}
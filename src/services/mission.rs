@@ -1,19 +1,32 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use chrono_tz::Tz;
 use diesel::{
-    ExpressionMethods, SelectableHelper,
+    BoolExpressionMethods, ExpressionMethods, SelectableHelper,
     query_dsl::methods::{FilterDsl, SelectDsl},
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
 use redis::AsyncCommands;
+use uuid::Uuid;
 
 use crate::{
     config::{cache::CacheConn, db::DbConn},
-    models::mission::{Mission, NewMission},
+    models::{
+        gift_reward::GiftReward,
+        mission::{Mission, MissionPeriod, MissionScheduleChangeset, NewMission, ReturnMissionProgress, UpdateMission},
+    },
     schema::missions,
     services::{
-        feature_usage::give_usage_count_to_user,
-        user::{give_exp_to_user, level_up},
+        category::get_place_type_tags,
+        exp_event::{get_exp_events, get_exp_multiplier},
+        exp_history::count_exp_history_by_source,
+        gift_reward::grant_gift_reward,
+        leaderboard::record_exp_on_leaderboards,
+        user::{get_user_by_id, give_exp_and_level_up, lock_user},
+    },
+    utils::time::{
+        get_seconds_to_midnight, get_seconds_to_next_month, get_seconds_to_next_week, get_start_of_month, get_start_of_today, get_start_of_week, get_this_month, get_this_week, get_today,
+        get_user_timezone,
     },
-    utils::time::{get_seconds_to_midnight, get_today},
 };
 
 pub async fn create_mission(conn: &mut DbConn, payload: &NewMission) -> Result<Mission, diesel::result::Error> {
@@ -28,10 +41,154 @@ pub async fn get_mission_by_code(conn: &mut DbConn, code: &str) -> Result<Missio
     missions::table.filter(missions::code.eq(code)).select(Mission::as_select()).first::<Mission>(conn).await
 }
 
+pub async fn get_mission_by_id(conn: &mut DbConn, id: Uuid) -> Result<Mission, diesel::result::Error> {
+    missions::table.filter(missions::id.eq(id)).select(Mission::as_select()).first::<Mission>(conn).await
+}
+
 pub async fn get_missions(conn: &mut DbConn) -> Result<Vec<Mission>, diesel::result::Error> {
     missions::table.select(Mission::as_select()).load(conn).await
 }
 
+/// Missions regular users can see: enabled and inside their active window at `now`.
+pub async fn get_available_missions(conn: &mut DbConn, now: NaiveDateTime) -> Result<Vec<Mission>, diesel::result::Error> {
+    missions::table
+        .filter(missions::is_disabled.eq(false))
+        .filter(missions::active_from.is_null().or(missions::active_from.le(now)))
+        .filter(missions::active_until.is_null().or(missions::active_until.gt(now)))
+        .select(Mission::as_select())
+        .load(conn)
+        .await
+}
+
+pub async fn update_mission(conn: &mut DbConn, id: Uuid, changeset: &UpdateMission) -> Result<Mission, diesel::result::Error> {
+    diesel::update(missions::table.filter(missions::id.eq(id)))
+        .set(changeset)
+        .returning(Mission::as_returning())
+        .get_result::<Mission>(conn)
+        .await
+}
+
+pub async fn update_mission_schedule(conn: &mut DbConn, id: Uuid, changeset: &MissionScheduleChangeset) -> Result<Mission, diesel::result::Error> {
+    diesel::update(missions::table.filter(missions::id.eq(id)))
+        .set(changeset)
+        .returning(Mission::as_returning())
+        .get_result::<Mission>(conn)
+        .await
+}
+
+pub async fn set_mission_disabled(conn: &mut DbConn, id: Uuid, is_disabled: bool) -> Result<Mission, diesel::result::Error> {
+    diesel::update(missions::table.filter(missions::id.eq(id)))
+        .set(missions::is_disabled.eq(is_disabled))
+        .returning(Mission::as_returning())
+        .get_result::<Mission>(conn)
+        .await
+}
+
+pub async fn delete_mission(conn: &mut DbConn, id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(missions::table.filter(missions::id.eq(id))).execute(conn).await
+}
+
+/// Cache key segment and expiry in seconds of the current `period` in the user's timezone `tz`,
+/// `None` for lifetime missions whose completions are counted from the `exp_history` ledger
+/// instead.
+pub fn get_period_window(period: MissionPeriod, tz: Tz) -> Option<(String, i64)> {
+    match period {
+        MissionPeriod::Daily => Some((get_today(tz), get_seconds_to_midnight(tz))),
+        MissionPeriod::Weekly => Some((get_this_week(tz), get_seconds_to_next_week(tz))),
+        MissionPeriod::Monthly => Some((get_this_month(tz), get_seconds_to_next_month(tz))),
+        MissionPeriod::Lifetime => None,
+    }
+}
+
+/// Start of the current `period` in `tz` as a UTC timestamp, `None` for lifetime periods.
+pub fn get_period_start(period: MissionPeriod, tz: Tz) -> Option<NaiveDateTime> {
+    match period {
+        MissionPeriod::Daily => Some(get_start_of_today(tz)),
+        MissionPeriod::Weekly => Some(get_start_of_week(tz)),
+        MissionPeriod::Monthly => Some(get_start_of_month(tz)),
+        MissionPeriod::Lifetime => None,
+    }
+}
+
+/// Completions of `mission` by the user in the period identified by `window`.
+async fn get_mission_count<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: &str, code: &str, window: Option<&(String, i64)>) -> Result<i32, String> {
+    match window {
+        Some((period_key, _)) => {
+            let cache_key = format!("mission:{}:{}", user_id, period_key);
+
+            let count: Option<i32> = cache_conn.hget(&cache_key, code).await.map_err(|err| err.to_string())?;
+
+            Ok(count.unwrap_or(0))
+        }
+        None => {
+            let user_uuid = Uuid::parse_str(user_id).map_err(|err| err.to_string())?;
+
+            Ok(count_exp_history_by_source(conn, user_uuid, code).await.map_err(|err| err.to_string())? as i32)
+        }
+    }
+}
+
+/// Merges `missions` with the user's completions in their current period, computed in the user's
+/// timezone `tz`.
+///
+/// # Returns
+/// For every mission, its completion count, the completions left (`None` when uncapped) and the
+/// UTC time its period resets.
+///
+pub async fn get_missions_progress<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: &str, tz: Tz, missions: Vec<Mission>) -> Result<Vec<ReturnMissionProgress>, String> {
+    let now = Utc::now().naive_utc();
+    let mut results = Vec::with_capacity(missions.len());
+
+    for mission in missions {
+        let period = MissionPeriod::parse(&mission.period).ok_or("Unknown mission period.")?;
+
+        let window = get_period_window(period, tz);
+
+        let completed_count = get_mission_count(conn, cache_conn, user_id, &mission.code, window.as_ref()).await?;
+
+        results.push(ReturnMissionProgress {
+            is_active: mission.is_active_at(now),
+            completed_count,
+            remaining: mission.max_per_day.map(|max| (max - completed_count).max(0)),
+            resets_at: window.map(|(_, expire_time)| now + Duration::seconds(expire_time)),
+            mission,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Reserves a completion slot in the cache hash of the period before any EXP is granted, so
+/// concurrent completions cannot exceed `max_per_day`.
+///
+/// # Returns
+/// - `Ok(count)` with the completion count including this one.
+/// - `Err(String)` if the period max is reached; the reservation is released.
+///
+async fn reserve_mission_slot<'a>(cache_conn: &mut CacheConn<'a>, cache_key: &str, code: &str, expire_time: i64, max_per_day: Option<i32>) -> Result<i32, String> {
+    let count: i32 = cache_conn.hincr(cache_key, code, 1).await.map_err(|err| err.to_string())?;
+
+    if count == 1 {
+        let _: i64 = cache_conn.expire(cache_key, expire_time).await.map_err(|err| err.to_string())?;
+    }
+
+    if max_per_day.is_some_and(|max| count > max) {
+        release_mission_slot(cache_conn, cache_key, code).await;
+
+        return Err("Mission already completed for this period.".into());
+    }
+
+    Ok(count)
+}
+
+async fn release_mission_slot<'a>(cache_conn: &mut CacheConn<'a>, cache_key: &str, code: &str) {
+    let result: Result<i32, _> = cache_conn.hincr(cache_key, code, -1).await;
+
+    if let Err(err) = result {
+        eprintln!("Failed to release mission reservation: {}", err);
+    }
+}
+
 /// Performs an user mission, updates EXP, handles level up, and tracks mission completion in
 /// cache
 ///
@@ -41,60 +198,120 @@ pub async fn get_missions(conn: &mut DbConn) -> Result<Vec<Mission>, diesel::res
 /// - `user_id`: The ID of the user performing the mission.
 /// - `code`: The mission's unique code string.
 /// - `scale`: Optional multiplier for EXP reward.
+/// - `place_types`: Types of the place the mission was performed on, empty if none.
 ///
 /// # Returns
 /// - `Ok(())` if the mission is performed successfully.
 /// - `Err(String)` if mission cannot be completed or on error.
 ///
 /// # Behavior
-/// - Checks how many times the user has completed this mission today using cache.
-/// - If the daily max is reached, returns an error.
-/// - Calculates EXP reward, optionally scaled.
-/// - Increments user's EXP and checks for level up; if level up, increases usage count as a gift.
-/// - Increments mission count in the cache hash.
-/// - Sets expiry on the cache has to midnight if this is the first completion today.
+/// - Refuses disabled missions and missions outside their `active_from` / `active_until` window.
+/// - Loads the active EXP events and the categories of the place before reserving anything, so
+///   that a failed lookup does not use up a completion slot.
+/// - Reserves a completion slot with `HINCRBY` before granting anything; if the period max is
+///   reached the reservation is released and an error is returned. Missions without
+///   `max_per_day` are uncapped.
+/// - Calculates EXP reward, optionally scaled, then multiplied by every active EXP event that
+///   applies to the mission and to the place it was performed on, by Google type or category.
+/// - In one transaction holding the user's row lock: increments user's EXP, recording the mission
+///   code as the `exp_history` source along with the event multiplier, applies level ups with their gift rewards and grants the
+///   mission's declared gift reward.
+/// - Releases the reservation if the transaction fails.
+/// - Adds the EXP to the leaderboards.
 ///
 /// # Caching
-/// Uses a hash with key format `mission:{user_id}:{today}`, where each field is mission code, and
-/// its value is the completion count for today.
-/// The hash expires at midnight.
+/// Uses a hash with key format `mission:{user_id}:{period_key}`, where each field is mission code,
+/// and its value is the completion count for the period. Period keys are `YYYY-MM-DD` for daily,
+/// `YYYY-Www` (ISO week) for weekly and `YYYY-MM` for monthly missions, all in the user's
+/// timezone; the hash expires when the period ends there. Lifetime missions are counted from
+/// `exp_history` under the user's row lock and never cached.
 ///
-pub async fn do_mission<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: &str, code: &str, scale: Option<i32>) -> Result<(), String> {
-    let today = get_today();
+pub async fn do_mission<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: &str, code: &str, scale: Option<i32>, place_types: &[String]) -> Result<(), String> {
+    let mission = get_mission_by_code(conn, code).await.map_err(|err| err.to_string())?;
 
-    let cache_key = format!("mission:{}:{}", user_id, today);
+    let now = Utc::now().naive_utc();
 
-    let current_count: Option<i32> = cache_conn.hget(&cache_key, code).await.map_err(|err| err.to_string())?;
+    if !mission.is_active_at(now) {
+        return Err("Mission is not active.".into());
+    }
 
-    let mission = get_mission_by_code(conn, code).await.map_err(|err| err.to_string())?;
+    let period = MissionPeriod::parse(&mission.period).ok_or("Unknown mission period.")?;
+
+    let user = get_user_by_id(conn, user_id).await.map_err(|err| err.to_string())?;
+
+    let user_uuid = user.id;
+
+    let events = get_exp_events(conn, now, false).await.map_err(|err| err.to_string())?;
 
-    if let Some(count) = current_count {
-        if count >= mission.max_per_day.unwrap_or(0) {
-            return Err("Mission already completed for today.".into());
+    let place_types = if !place_types.is_empty() && events.iter().any(|event| event.place_type.is_some()) {
+        get_place_type_tags(conn, place_types).await.map_err(|err| err.to_string())?
+    } else {
+        place_types.to_vec()
+    };
+
+    let reservation = match get_period_window(period, get_user_timezone(&user.timezone)) {
+        Some((period_key, expire_time)) => {
+            let cache_key = format!("mission:{}:{}", user_id, period_key);
+
+            reserve_mission_slot(cache_conn, &cache_key, code, expire_time, mission.max_per_day).await?;
+
+            Some(cache_key)
         }
-    }
+        None => None,
+    };
 
-    let exp_reward = {
+    let base_exp_reward = {
         match scale {
             Some(num) => mission.exp_reward * num,
             None => mission.exp_reward,
         }
     };
 
-    give_exp_to_user(conn, user_id, exp_reward).await.map_err(|err| err.to_string())?;
+    let multiplier = get_exp_multiplier(&events, code, &place_types);
 
-    let is_up = level_up(conn, user_id).await.map_err(|err| err.to_string())?;
+    let exp_reward = (base_exp_reward as f64 * multiplier).round() as i32;
 
-    if is_up {
-        give_usage_count_to_user(conn, user_id, 1).await.map_err(|err| err.to_string())?;
-    }
+    let gift = GiftReward::parse(mission.gift_reward_type.as_deref(), mission.gift_reward_count, mission.gift_reward_code.as_deref());
+
+    let is_lifetime = reservation.is_none();
+
+    let result = conn
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                lock_user(conn, user_uuid).await?;
+
+                if is_lifetime
+                    && let Some(max) = mission.max_per_day
+                    && count_exp_history_by_source(conn, user_uuid, &mission.code).await? >= max as i64
+                {
+                    return Err(diesel::result::Error::RollbackTransaction);
+                }
 
-    let _: i32 = cache_conn.hincr(&cache_key, code, 1).await.map_err(|err| err.to_string())?;
+                give_exp_and_level_up(conn, user_id, exp_reward, &mission.code, multiplier).await?;
 
-    if current_count.is_none() {
-        let expire_time = get_seconds_to_midnight();
+                if let Some(gift) = &gift {
+                    grant_gift_reward(conn, user_uuid, gift, &mission.code).await?;
+                }
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await;
+
+    if let Err(err) = result {
+        if let Some(cache_key) = &reservation {
+            release_mission_slot(cache_conn, cache_key, code).await;
+        }
+
+        return Err(match err {
+            diesel::result::Error::RollbackTransaction => "Mission already completed for this period.".into(),
+            err => err.to_string(),
+        });
+    }
 
-        let _: i64 = cache_conn.expire(&cache_key, expire_time).await.map_err(|err| err.to_string())?;
+    if let Err(err) = record_exp_on_leaderboards(cache_conn, user_id, exp_reward).await {
+        eprintln!("Failed to update leaderboards: {}", err);
     }
 
     Ok(())
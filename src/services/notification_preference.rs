@@ -0,0 +1,82 @@
+use chrono::NaiveTime;
+use diesel::{
+    ExpressionMethods, OptionalExtension, SelectableHelper,
+    query_dsl::methods::{FilterDsl, SelectDsl},
+};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::notification_preference::{NewNotificationPreference, NotificationPreference},
+    schema::notification_preferences,
+    services::{email_unsubscribe::is_email_unsubscribed, user::get_user_by_email},
+    utils::time::{get_time_now, get_user_timezone, is_within_quiet_hours},
+};
+
+pub async fn get_notification_preferences_by_user(conn: &mut DbConn, user_id: Uuid) -> Result<Vec<NotificationPreference>, diesel::result::Error> {
+    notification_preferences::table
+        .filter(notification_preferences::user_id.eq(user_id))
+        .select(NotificationPreference::as_select())
+        .load(conn)
+        .await
+}
+
+pub async fn upsert_notification_preference(conn: &mut DbConn, payload: &NewNotificationPreference) -> Result<NotificationPreference, diesel::result::Error> {
+    diesel::insert_into(notification_preferences::table)
+        .values(payload)
+        .on_conflict((notification_preferences::user_id, notification_preferences::category))
+        .do_update()
+        .set(payload)
+        .returning(NotificationPreference::as_returning())
+        .get_result::<NotificationPreference>(conn)
+        .await
+}
+
+/// Decides from a user's stored `email` preference for `category` and their `quiet_hours` whether
+/// an email of `category` may be sent at their local time `now`, see `is_email_allowed`.
+pub fn is_email_preference_allowed(email: Option<bool>, category: &str, quiet_hours: Option<(NaiveTime, NaiveTime)>, now: NaiveTime) -> bool {
+    if email == Some(false) {
+        return false;
+    }
+
+    category == "account" || quiet_hours.is_none_or(|(start, end)| !is_within_quiet_hours(start, end, now))
+}
+
+/// Decides whether an email of `category` may be sent to `email`.
+///
+/// # Behavior
+/// - Addresses that used a one-click unsubscribe link are never emailed for that category,
+///   whether or not they belong to a user.
+/// - Users without a stored preference for the category receive it.
+/// - Outside the `account` category, nothing is sent during the user's quiet hours.
+///
+/// In-app and push preferences are stored alongside but enforced by the clients.
+///
+pub async fn is_email_allowed(conn: &mut DbConn, email: &str, category: &str) -> Result<bool, diesel::result::Error> {
+    if is_email_unsubscribed(conn, email, category).await? {
+        return Ok(false);
+    }
+
+    let user = match get_user_by_email(conn, email).await.optional()? {
+        Some(user) => user,
+        None => return Ok(true),
+    };
+
+    let preference = notification_preferences::table
+        .filter(notification_preferences::user_id.eq(user.id))
+        .filter(notification_preferences::category.eq(category))
+        .select(NotificationPreference::as_select())
+        .first::<NotificationPreference>(conn)
+        .await
+        .optional()?;
+
+    let quiet_hours = user.quiet_hours_start.zip(user.quiet_hours_end);
+
+    Ok(is_email_preference_allowed(
+        preference.map(|preference| preference.email),
+        category,
+        quiet_hours,
+        get_time_now(get_user_timezone(&user.timezone)),
+    ))
+}
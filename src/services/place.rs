@@ -1,15 +1,149 @@
+use chrono::{DurationRound, NaiveDateTime, TimeDelta, Utc};
 use diesel::{
-    ExpressionMethods, SelectableHelper,
-    query_dsl::methods::{FilterDsl, SelectDsl},
+    BoolExpressionMethods, ExpressionMethods, PgArrayExpressionMethods, QueryDsl, SelectableHelper, define_sql_function,
+    pg::Pg,
+    sql_types::{Double, Jsonb, Nullable, Text, Timestamp},
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
 
 use crate::{
     config::db::DbConn,
-    models::place::{NewPlace, Place},
-    schema::places,
+    models::{
+        place::{NewPlace, NewPlaceViewBucket, Place, PlaceChangeset, PlaceFilter},
+        review::NewReview,
+    },
+    schema::{place_view_buckets, places, user_place_access},
+    services::review::create_missing_google_reviews,
 };
 
+/// Meters per degree of latitude.
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// Age after which the Google data of a place is refreshed from upserted data.
+const PLACE_STALE_AFTER_HOURS: i64 = 24;
+
+/// Views older than this no longer count towards the trending score.
+const TRENDING_WINDOW_DAYS: i64 = 7;
+
+/// Age after which a view weighs half as much in the trending score.
+const TRENDING_HALF_LIFE_HOURS: f64 = 24.0;
+
+define_sql_function! {
+    /// Great-circle distance in meters, defined in the `add_coordinates_place` migration.
+    fn distance_meters(lat1: Nullable<Double>, lng1: Nullable<Double>, lat2: Double, lng2: Double) -> Nullable<Double>;
+}
+
+define_sql_function! {
+    /// Whether a place is open at the UTC time `at`, defined in the `add_opening_hours_place` migration.
+    fn is_open_at(opening_hours: Nullable<Jsonb>, utc_offset: Nullable<Text>, at: Timestamp) -> Nullable<Bool>;
+}
+
+/// Latitude and longitude half-widths of the bounding box around a circle of `radius` meters
+/// centered at latitude `lat`.
+fn get_coordinate_deltas(lat: f64, radius: f64) -> (f64, f64) {
+    let lat_delta = radius / METERS_PER_DEGREE;
+    let lng_delta = (radius / (METERS_PER_DEGREE * lat.to_radians().cos().max(0.01))).min(180.0);
+
+    (lat_delta, lng_delta)
+}
+
+/// Longitude ranges of the bounding box spanning `lng_delta` degrees on each side of longitude
+/// `lng`.
+///
+/// # Returns
+/// The range west of the antimeridian, and the range east of it when the box crosses it.
+pub fn get_longitude_ranges(lng: f64, lng_delta: f64) -> ((f64, f64), Option<(f64, f64)>) {
+    let (min, max) = (lng - lng_delta, lng + lng_delta);
+
+    if lng_delta >= 180.0 {
+        ((-180.0, 180.0), None)
+    } else if min < -180.0 {
+        ((min + 360.0, 180.0), Some((-180.0, max)))
+    } else if max > 180.0 {
+        ((min, 180.0), Some((-180.0, max - 360.0)))
+    } else {
+        ((min, max), None)
+    }
+}
+
+/// Restricts `query` to the places in the bounding box around a circle of `radius` meters centered
+/// at (`lat`, `lng`).
+fn filter_bounding_box(query: places::BoxedQuery<'_, Pg>, lat: f64, lng: f64, radius: f64) -> places::BoxedQuery<'_, Pg> {
+    let (lat_delta, lng_delta) = get_coordinate_deltas(lat, radius);
+
+    let query = query.filter(places::latitude.between(lat - lat_delta, lat + lat_delta));
+
+    match get_longitude_ranges(lng, lng_delta) {
+        ((min, max), None) => query.filter(places::longitude.between(min, max)),
+        ((west_min, west_max), Some((east_min, east_max))) => query.filter(places::longitude.between(west_min, west_max).or(places::longitude.between(east_min, east_max))),
+    }
+}
+
+/// Restricts `query` to the places matching `filter`.
+fn filter_places<'a>(mut query: places::BoxedQuery<'a, Pg>, filter: PlaceFilter<'a>) -> places::BoxedQuery<'a, Pg> {
+    if let Some(place_types) = filter.place_types {
+        query = query.filter(places::types.overlaps_with(place_types.iter().cloned().map(Some).collect::<Vec<_>>()));
+    }
+
+    if let Some(open_at) = filter.open_at {
+        query = query.filter(is_open_at(places::opening_hours, places::utc_offset, open_at).eq(true));
+    }
+
+    query
+}
+
+/// Places within `radius` meters of (`lat`, `lng`), matching `filter`, nearest first.
+///
+/// # Returns
+/// The page of places with their distance in meters, and the total count of matching places.
+///
+/// # Behavior
+/// Candidates are narrowed with a bounding box on the indexed `latitude` / `longitude` columns
+/// before the exact distance is computed. The box is split in two when it crosses the antimeridian.
+///
+pub async fn get_nearby_places(conn: &mut DbConn, lat: f64, lng: f64, radius: f64, filter: PlaceFilter<'_>, limit: i64, offset: i64) -> Result<(Vec<(Place, f64)>, i64), diesel::result::Error> {
+    let distance = distance_meters(places::latitude, places::longitude, lat, lng);
+
+    let build_query = || {
+        let query = filter_bounding_box(places::table.filter(distance.le(radius)).into_boxed(), lat, lng, radius);
+
+        filter_places(query, filter)
+    };
+
+    let total = build_query().count().get_result::<i64>(conn).await?;
+
+    let places: Vec<(Place, Option<f64>)> = build_query()
+        .order(distance.asc())
+        .limit(limit)
+        .offset(offset)
+        .select((Place::as_select(), distance))
+        .load(conn)
+        .await?;
+
+    Ok((places.into_iter().map(|(place, distance)| (place, distance.unwrap_or_default())).collect(), total))
+}
+
+/// Up to `limit` places within `radius` meters of (`lat`, `lng`), matching `filter`, that
+/// the user `user_id` never visited, nearest first, with their distance in meters.
+pub async fn get_unvisited_nearby_places(conn: &mut DbConn, user_id: Uuid, lat: f64, lng: f64, radius: f64, filter: PlaceFilter<'_>, limit: i64) -> Result<Vec<(Place, f64)>, diesel::result::Error> {
+    let distance = distance_meters(places::latitude, places::longitude, lat, lng);
+
+    let visited_place_ids = user_place_access::table.filter(user_place_access::user_id.eq(user_id)).select(user_place_access::place_id);
+
+    let query = places::table.filter(distance.le(radius)).filter(places::id.ne_all(visited_place_ids)).into_boxed();
+
+    let query = filter_places(filter_bounding_box(query, lat, lng, radius), filter);
+
+    let places: Vec<(Place, Option<f64>)> = query.order(distance.asc()).limit(limit).select((Place::as_select(), distance)).load(conn).await?;
+
+    Ok(places.into_iter().map(|(place, distance)| (place, distance.unwrap_or_default())).collect())
+}
+
+pub async fn get_place_by_id(conn: &mut DbConn, id: Uuid) -> Result<Place, diesel::result::Error> {
+    places::table.filter(places::id.eq(id)).select(Place::as_select()).first::<Place>(conn).await
+}
+
 pub async fn get_place_by_place_id(conn: &mut DbConn, place_id: &str) -> Result<Place, diesel::result::Error> {
     places::table.filter(places::place_id.eq(place_id)).select(Place::as_select()).first::<Place>(conn).await
 }
@@ -18,10 +152,150 @@ pub async fn create_place(conn: &mut DbConn, payload: &NewPlace) -> Result<Place
     diesel::insert_into(places::table).values(payload).returning(Place::as_returning()).get_result::<Place>(conn).await
 }
 
+/// Whether a place last refreshed at `updated_at` should be refreshed with data fetched from Google
+/// at `fetched_at`, i.e. the data is newer than the place by more than `PLACE_STALE_AFTER_HOURS`.
+pub fn is_place_stale(updated_at: NaiveDateTime, fetched_at: NaiveDateTime) -> bool {
+    fetched_at - updated_at >= TimeDelta::hours(PLACE_STALE_AFTER_HOURS)
+}
+
+/// Updates the Google data of the place `id` with `changeset` and stores the Google reviews of
+/// `reviews` it does not have yet.
+///
+/// # Behavior
+/// Fields set by approved edit suggestions are kept, since they take precedence over Google data.
+///
+pub async fn refresh_place(conn: &mut DbConn, id: Uuid, changeset: PlaceChangeset, reviews: &[NewReview]) -> Result<Place, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let overridden_fields: Vec<String> = diesel::QueryDsl::for_update(places::table.filter(places::id.eq(id)))
+                .select(places::overridden_fields)
+                .first(conn)
+                .await?;
+
+            let place = diesel::update(places::table.filter(places::id.eq(id)))
+                .set(changeset.without_fields(&overridden_fields))
+                .returning(Place::as_returning())
+                .get_result::<Place>(conn)
+                .await?;
+
+            create_missing_google_reviews(conn, place.id, reviews).await?;
+
+            Ok(place)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Increments the all-time view count of the place and records the view in the current hourly
+/// bucket used for the trending score.
 pub async fn increase_place_view(conn: &mut DbConn, place_id: &str) -> Result<Place, diesel::result::Error> {
-    diesel::update(places::table.filter(places::place_id.eq(place_id)))
-        .set(places::range_time_view_count.eq(places::range_time_view_count + 1))
-        .returning(Place::as_returning())
-        .get_result::<Place>(conn)
-        .await
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let place = diesel::update(places::table.filter(places::place_id.eq(place_id)))
+                .set(places::range_time_view_count.eq(places::range_time_view_count + 1))
+                .returning(Place::as_returning())
+                .get_result::<Place>(conn)
+                .await?;
+
+            let now = Utc::now().naive_utc();
+
+            let new_bucket = NewPlaceViewBucket {
+                place_id: place.id,
+                bucket_start: now.duration_trunc(TimeDelta::hours(1)).unwrap_or(now),
+                view_count: 1,
+            };
+
+            diesel::insert_into(place_view_buckets::table)
+                .values(&new_bucket)
+                .on_conflict((place_view_buckets::place_id, place_view_buckets::bucket_start))
+                .do_update()
+                .set(place_view_buckets::view_count.eq(place_view_buckets::view_count + 1))
+                .execute(conn)
+                .await?;
+
+            Ok(place)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Places with views in the trending window, optionally within `radius` meters of (`lat`, `lng`)
+/// given as `region`, and matching `filter`, highest trending score first.
+///
+/// # Returns
+/// The page of places and the total count of matching places.
+///
+pub async fn get_trending_places(conn: &mut DbConn, region: Option<(f64, f64, f64)>, filter: PlaceFilter<'_>, limit: i64, offset: i64) -> Result<(Vec<Place>, i64), diesel::result::Error> {
+    let build_query = || {
+        let mut query = places::table.filter(places::trending_score.gt(0.0)).into_boxed();
+
+        if let Some((lat, lng, radius)) = region {
+            query = filter_bounding_box(query, lat, lng, radius).filter(distance_meters(places::latitude, places::longitude, lat, lng).le(radius));
+        }
+
+        filter_places(query, filter)
+    };
+
+    let total = build_query().count().get_result::<i64>(conn).await?;
+
+    let places = build_query()
+        .order((places::trending_score.desc(), places::id.asc()))
+        .limit(limit)
+        .offset(offset)
+        .select(Place::as_select())
+        .load(conn)
+        .await?;
+
+    Ok((places, total))
+}
+
+/// Recomputes the trending score of every place as the sum of its views in the trending window,
+/// each halved for every `TRENDING_HALF_LIFE_HOURS` of age at `now`.
+///
+/// # Returns
+/// The number of places whose score was updated.
+///
+/// # Behavior
+/// Buckets older than the trending window are deleted, and places without remaining buckets are
+/// reset to a score of 0.
+///
+pub async fn refresh_trending_scores(conn: &mut DbConn, now: NaiveDateTime) -> Result<usize, diesel::result::Error> {
+    let window_start = now - TimeDelta::days(TRENDING_WINDOW_DAYS);
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            diesel::delete(place_view_buckets::table.filter(place_view_buckets::bucket_start.lt(window_start)))
+                .execute(conn)
+                .await?;
+
+            let reset_count = diesel::update(
+                places::table
+                    .filter(places::trending_score.ne(0.0))
+                    .filter(diesel::dsl::not(diesel::dsl::exists(place_view_buckets::table.filter(place_view_buckets::place_id.eq(places::id))))),
+            )
+            .set(places::trending_score.eq(0.0))
+            .execute(conn)
+            .await?;
+
+            let scored_count = diesel::sql_query(
+                "update places set trending_score = scores.score \
+                 from ( \
+                   select place_id, sum(view_count * power(0.5, extract(epoch from ($1 - bucket_start)) / 3600 / $2)) as score \
+                   from place_view_buckets \
+                   group by place_id \
+                 ) as scores \
+                 where places.id = scores.place_id",
+            )
+            .bind::<Timestamp, _>(now)
+            .bind::<Double, _>(TRENDING_HALF_LIFE_HOURS)
+            .execute(conn)
+            .await?;
+
+            Ok(reset_count + scored_count)
+        }
+        .scope_boxed()
+    })
+    .await
 }
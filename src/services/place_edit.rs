@@ -0,0 +1,178 @@
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::{
+        place::Place,
+        place_edit::{NewPlaceEditEvent, NewPlaceEditSuggestion, PlaceEditEvent, PlaceEditField, PlaceEditStatus, PlaceEditSuggestion},
+    },
+    schema::{place_edit_events, place_edit_suggestions, places},
+};
+
+/// Records the suggestions of `payload`, all for the place `place`, with an audit entry holding
+/// the value each one would replace.
+pub async fn create_place_edit_suggestions(conn: &mut DbConn, place: &Place, payload: &[NewPlaceEditSuggestion]) -> Result<Vec<PlaceEditSuggestion>, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let suggestions = diesel::insert_into(place_edit_suggestions::table)
+                .values(payload)
+                .returning(PlaceEditSuggestion::as_returning())
+                .get_results::<PlaceEditSuggestion>(conn)
+                .await?;
+
+            let events: Vec<NewPlaceEditEvent> = suggestions
+                .iter()
+                .map(|suggestion| NewPlaceEditEvent {
+                    suggestion_id: suggestion.id,
+                    actor_id: suggestion.user_id,
+                    action: PlaceEditStatus::Pending.as_str().to_string(),
+                    old_value: PlaceEditField::parse(&suggestion.field).and_then(|field| field.get(place)),
+                    new_value: suggestion.proposed_value.clone(),
+                    note: suggestion.reason.clone(),
+                })
+                .collect();
+
+            diesel::insert_into(place_edit_events::table).values(&events).execute(conn).await?;
+
+            Ok(suggestions)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Suggestions with `status` and their place, oldest first.
+///
+/// # Returns
+/// The page of suggestions and the total count of suggestions with `status`.
+///
+pub async fn get_place_edit_suggestions(conn: &mut DbConn, status: PlaceEditStatus, limit: i64, offset: i64) -> Result<(Vec<(PlaceEditSuggestion, Place)>, i64), diesel::result::Error> {
+    let total = place_edit_suggestions::table
+        .filter(place_edit_suggestions::status.eq(status.as_str()))
+        .count()
+        .get_result::<i64>(conn)
+        .await?;
+
+    let suggestions = place_edit_suggestions::table
+        .inner_join(places::table)
+        .filter(place_edit_suggestions::status.eq(status.as_str()))
+        .order(place_edit_suggestions::created_at.asc())
+        .limit(limit)
+        .offset(offset)
+        .select((PlaceEditSuggestion::as_select(), Place::as_select()))
+        .load(conn)
+        .await?;
+
+    Ok((suggestions, total))
+}
+
+pub async fn get_place_edit_suggestion_by_id(conn: &mut DbConn, id: Uuid) -> Result<(PlaceEditSuggestion, Place), diesel::result::Error> {
+    place_edit_suggestions::table
+        .inner_join(places::table)
+        .filter(place_edit_suggestions::id.eq(id))
+        .select((PlaceEditSuggestion::as_select(), Place::as_select()))
+        .first(conn)
+        .await
+}
+
+pub async fn get_place_edit_events(conn: &mut DbConn, suggestion_id: Uuid) -> Result<Vec<PlaceEditEvent>, diesel::result::Error> {
+    place_edit_events::table
+        .filter(place_edit_events::suggestion_id.eq(suggestion_id))
+        .order(place_edit_events::created_at.asc())
+        .select(PlaceEditEvent::as_select())
+        .load(conn)
+        .await
+}
+
+/// Sets `field` of the place `place_id` to `value`.
+async fn set_place_field(conn: &mut DbConn, place_id: Uuid, field: PlaceEditField, value: Option<String>) -> Result<usize, diesel::result::Error> {
+    let target = places::table.filter(places::id.eq(place_id));
+
+    match field {
+        PlaceEditField::Name => diesel::update(target).set(places::name.eq(value.unwrap_or_default())).execute(conn).await,
+        PlaceEditField::FormattedAddress => diesel::update(target).set(places::formatted_address.eq(value)).execute(conn).await,
+        PlaceEditField::FormattedPhoneNumber => diesel::update(target).set(places::formatted_phone_number.eq(value)).execute(conn).await,
+        PlaceEditField::BusinessStatus => diesel::update(target).set(places::business_status.eq(value)).execute(conn).await,
+        PlaceEditField::Website => diesel::update(target).set(places::website.eq(value)).execute(conn).await,
+    }
+}
+
+/// Approves or rejects the pending suggestion `id` on behalf of the admin `reviewer_id`.
+///
+/// # Behavior
+/// - Approving applies the proposed value to the place and marks the field as overridden, so that
+///   later Google refreshes keep it.
+/// - The suggestion and its place are locked, and the decision, the place update and the audit
+///   entry are committed together.
+/// - Fails with `RollbackTransaction` when the suggestion was already reviewed.
+///
+pub async fn review_place_edit_suggestion(conn: &mut DbConn, id: Uuid, reviewer_id: Uuid, status: PlaceEditStatus, note: Option<String>) -> Result<PlaceEditSuggestion, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let suggestion = diesel::QueryDsl::for_update(place_edit_suggestions::table.filter(place_edit_suggestions::id.eq(id)))
+                .select(PlaceEditSuggestion::as_select())
+                .first::<PlaceEditSuggestion>(conn)
+                .await?;
+
+            if suggestion.status != PlaceEditStatus::Pending.as_str() {
+                return Err(diesel::result::Error::RollbackTransaction);
+            }
+
+            let (old_value, new_value) = if status == PlaceEditStatus::Approved {
+                let place = diesel::QueryDsl::for_update(places::table.filter(places::id.eq(suggestion.place_id)))
+                    .select(Place::as_select())
+                    .first::<Place>(conn)
+                    .await?;
+
+                let field = PlaceEditField::parse(&suggestion.field).ok_or(diesel::result::Error::RollbackTransaction)?;
+
+                let old_value = field.get(&place);
+
+                set_place_field(conn, place.id, field, suggestion.proposed_value.clone()).await?;
+
+                if !place.overridden_fields.iter().any(|overridden| overridden == field.as_str()) {
+                    let mut overridden_fields = place.overridden_fields.clone();
+                    overridden_fields.push(field.as_str().to_string());
+
+                    diesel::update(places::table.filter(places::id.eq(place.id)))
+                        .set(places::overridden_fields.eq(overridden_fields))
+                        .execute(conn)
+                        .await?;
+                }
+
+                (old_value, suggestion.proposed_value.clone())
+            } else {
+                (None, None)
+            };
+
+            let suggestion = diesel::update(place_edit_suggestions::table.filter(place_edit_suggestions::id.eq(id)))
+                .set((
+                    place_edit_suggestions::status.eq(status.as_str()),
+                    place_edit_suggestions::reviewed_by.eq(reviewer_id),
+                    place_edit_suggestions::reviewed_at.eq(Utc::now().naive_utc()),
+                    place_edit_suggestions::review_note.eq(&note),
+                ))
+                .returning(PlaceEditSuggestion::as_returning())
+                .get_result::<PlaceEditSuggestion>(conn)
+                .await?;
+
+            let event = NewPlaceEditEvent {
+                suggestion_id: suggestion.id,
+                actor_id: reviewer_id,
+                action: status.as_str().to_string(),
+                old_value,
+                new_value,
+                note,
+            };
+
+            diesel::insert_into(place_edit_events::table).values(&event).execute(conn).await?;
+
+            Ok(suggestion)
+        }
+        .scope_boxed()
+    })
+    .await
+}
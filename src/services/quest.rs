@@ -0,0 +1,279 @@
+use chrono::{NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use diesel::{ExpressionMethods, OptionalExtension, PgArrayExpressionMethods, QueryDsl, SelectableHelper, dsl::count_distinct};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::{cache::CacheConn, db::DbConn},
+    models::{
+        mission::MissionPeriod,
+        quest::{NewQuest, NewQuestStep, NewUserQuest, Quest, QuestAction, QuestStep, ReturnQuest, ReturnQuestStep, UserQuest},
+    },
+    schema::{places, quest_steps, quests, reviews, user_place_access, user_quests},
+    services::{
+        category::{get_matching_category_codes, get_matching_google_types},
+        mission::{do_mission, get_period_start, get_period_window},
+        user_place_access::count_daily_visits,
+    },
+};
+
+/// Period key of lifetime quests, which are completed at most once.
+const LIFETIME_PERIOD_KEY: &str = "lifetime";
+
+pub async fn create_quest(conn: &mut DbConn, payload: &NewQuest) -> Result<Quest, diesel::result::Error> {
+    diesel::insert_into(quests::table).values(payload).returning(Quest::as_returning()).get_result::<Quest>(conn).await
+}
+
+pub async fn create_quest_steps(conn: &mut DbConn, payload: &[NewQuestStep]) -> Result<Vec<QuestStep>, diesel::result::Error> {
+    diesel::insert_into(quest_steps::table)
+        .values(payload)
+        .returning(QuestStep::as_returning())
+        .get_results::<QuestStep>(conn)
+        .await
+}
+
+pub async fn get_quests(conn: &mut DbConn) -> Result<Vec<Quest>, diesel::result::Error> {
+    quests::table.order(quests::created_at.asc()).select(Quest::as_select()).load(conn).await
+}
+
+pub async fn get_quest_steps(conn: &mut DbConn, quest_ids: &[Uuid]) -> Result<Vec<QuestStep>, diesel::result::Error> {
+    quest_steps::table
+        .filter(quest_steps::quest_id.eq_any(quest_ids))
+        .order((quest_steps::position.asc(), quest_steps::id.asc()))
+        .select(QuestStep::as_select())
+        .load(conn)
+        .await
+}
+
+pub async fn get_user_quest(conn: &mut DbConn, user_id: Uuid, quest_id: Uuid, period_key: &str) -> Result<Option<UserQuest>, diesel::result::Error> {
+    user_quests::table
+        .filter(user_quests::user_id.eq(user_id))
+        .filter(user_quests::quest_id.eq(quest_id))
+        .filter(user_quests::period_key.eq(period_key))
+        .select(UserQuest::as_select())
+        .first::<UserQuest>(conn)
+        .await
+        .optional()
+}
+
+/// Marks the user quest `id` completed at `now` unless it already is.
+///
+/// # Returns
+/// Whether this call completed it, and so is the one to reward it.
+///
+pub async fn claim_user_quest_completion(conn: &mut DbConn, id: Uuid, now: NaiveDateTime) -> Result<bool, diesel::result::Error> {
+    let updated = diesel::update(user_quests::table.filter(user_quests::id.eq(id)).filter(user_quests::completed_at.is_null()))
+        .set(user_quests::completed_at.eq(now))
+        .execute(conn)
+        .await?;
+
+    Ok(updated > 0)
+}
+
+/// Reverts a claimed completion whose reward failed, so that it is retried.
+pub async fn release_user_quest_completion(conn: &mut DbConn, id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::update(user_quests::table.filter(user_quests::id.eq(id)))
+        .set(user_quests::completed_at.eq(None::<NaiveDateTime>))
+        .execute(conn)
+        .await
+}
+
+pub async fn upsert_user_quest(conn: &mut DbConn, payload: &NewUserQuest) -> Result<UserQuest, diesel::result::Error> {
+    diesel::insert_into(user_quests::table)
+        .values(payload)
+        .on_conflict((user_quests::user_id, user_quests::quest_id, user_quests::period_key))
+        .do_update()
+        .set(payload)
+        .returning(UserQuest::as_returning())
+        .get_result::<UserQuest>(conn)
+        .await
+}
+
+/// Count of the user's actions matching `step` since `since`, or over all time when `None`.
+///
+/// # Behavior
+/// A place counts once per day towards visit steps, or once overall when the step is distinct.
+///
+pub async fn get_step_progress(conn: &mut DbConn, user_id: Uuid, step: &QuestStep, since: Option<NaiveDateTime>) -> Result<i64, diesel::result::Error> {
+    match QuestAction::parse(&step.action) {
+        Some(QuestAction::Visit) => {
+            let mut query = user_place_access::table.filter(user_place_access::user_id.eq(user_id)).into_boxed();
+
+            if let Some(place_type) = &step.place_type {
+                let category_codes = get_matching_category_codes(conn, place_type).await?;
+
+                query = query.filter(user_place_access::type_.eq_any(category_codes));
+            }
+
+            if let Some(since) = since {
+                query = query.filter(user_place_access::created_at.ge(since));
+            }
+
+            if step.is_distinct {
+                query.select(count_distinct(user_place_access::place_id)).get_result::<i64>(conn).await
+            } else {
+                query.select(count_daily_visits()).get_result::<i64>(conn).await
+            }
+        }
+        Some(QuestAction::Review) => {
+            let mut query = reviews::table.inner_join(places::table).filter(reviews::user_id.eq(user_id)).into_boxed();
+
+            if let Some(place_type) = &step.place_type {
+                let google_types: Vec<Option<String>> = get_matching_google_types(conn, place_type).await?.into_iter().map(Some).collect();
+
+                query = query.filter(places::types.overlaps_with(google_types));
+            }
+
+            if let Some(since) = since {
+                query = query.filter(reviews::created_at.ge(since));
+            }
+
+            if step.is_distinct {
+                query.select(count_distinct(reviews::place_id)).get_result::<i64>(conn).await
+            } else {
+                query.count().get_result::<i64>(conn).await
+            }
+        }
+        None => Ok(0),
+    }
+}
+
+/// Period key and start of the quest's current period in the user's timezone `tz`.
+fn get_quest_period(quest: &Quest, tz: Tz) -> Option<(String, Option<NaiveDateTime>)> {
+    let period = MissionPeriod::parse(&quest.period)?;
+
+    let period_key = get_period_window(period, tz).map(|(period_key, _)| period_key).unwrap_or(LIFETIME_PERIOD_KEY.to_string());
+
+    Some((period_key, get_period_start(period, tz)))
+}
+
+/// Active quests with the user's tracked progress on each step in the current period.
+pub async fn get_quests_progress(conn: &mut DbConn, user_id: Uuid, tz: Tz) -> Result<Vec<ReturnQuest>, diesel::result::Error> {
+    let now = Utc::now().naive_utc();
+
+    let quests: Vec<Quest> = get_quests(conn).await?.into_iter().filter(|quest| quest.is_active_at(now)).collect();
+
+    let quest_ids: Vec<Uuid> = quests.iter().map(|quest| quest.id).collect();
+
+    let steps = get_quest_steps(conn, &quest_ids).await?;
+
+    let mut results = Vec::with_capacity(quests.len());
+
+    for quest in quests {
+        let Some((period_key, _)) = get_quest_period(&quest, tz) else {
+            continue;
+        };
+
+        let user_quest = get_user_quest(conn, user_id, quest.id, &period_key).await?;
+
+        let progress = user_quest.as_ref().map(|user_quest| user_quest.progress.as_slice()).unwrap_or_default();
+
+        let return_steps = steps
+            .iter()
+            .filter(|step| step.quest_id == quest.id)
+            .enumerate()
+            .map(|(index, step)| ReturnQuestStep {
+                progress: progress.get(index).copied().unwrap_or(0),
+                step: step.clone(),
+            })
+            .collect();
+
+        results.push(ReturnQuest {
+            quest,
+            steps: return_steps,
+            completed_at: user_quest.and_then(|user_quest| user_quest.completed_at),
+        });
+    }
+
+    Ok(results)
+}
+
+/// Updates the user's progress on every active quest with a step counting `action`, and completes
+/// the quests whose steps are all met by performing their mission, which grants the rewards.
+///
+/// # Returns
+/// The quests completed by this call.
+///
+/// # Behavior
+/// - Quests already completed in their current period are skipped.
+/// - Completion is claimed on the user quest row before performing the mission, so concurrent
+///   actions reward a quest once. A failed reward releases the claim and is retried on the next
+///   matching action.
+///
+pub async fn evaluate_quests<'a>(conn: &mut DbConn, cache_conn: &mut CacheConn<'a>, user_id: Uuid, tz: Tz, action: QuestAction) -> Result<Vec<Quest>, String> {
+    let now = Utc::now().naive_utc();
+
+    let candidate_ids: Vec<Uuid> = quest_steps::table
+        .filter(quest_steps::action.eq(action.as_str()))
+        .select(quest_steps::quest_id)
+        .distinct()
+        .load(conn)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let quests: Vec<Quest> = quests::table
+        .filter(quests::id.eq_any(&candidate_ids))
+        .select(Quest::as_select())
+        .load::<Quest>(conn)
+        .await
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .filter(|quest| quest.is_active_at(now))
+        .collect();
+
+    let quest_ids: Vec<Uuid> = quests.iter().map(|quest| quest.id).collect();
+
+    let steps = get_quest_steps(conn, &quest_ids).await.map_err(|err| err.to_string())?;
+
+    let mut completed = vec![];
+
+    for quest in quests {
+        let Some((period_key, since)) = get_quest_period(&quest, tz) else {
+            continue;
+        };
+
+        let user_quest = get_user_quest(conn, user_id, quest.id, &period_key).await.map_err(|err| err.to_string())?;
+
+        if user_quest.is_some_and(|user_quest| user_quest.completed_at.is_some()) {
+            continue;
+        }
+
+        let quest_steps: Vec<&QuestStep> = steps.iter().filter(|step| step.quest_id == quest.id).collect();
+
+        let mut progress = Vec::with_capacity(quest_steps.len());
+
+        for step in &quest_steps {
+            let count = get_step_progress(conn, user_id, step, since).await.map_err(|err| err.to_string())?;
+
+            progress.push(count.min(step.target_count as i64) as i32);
+        }
+
+        let is_completed = !quest_steps.is_empty() && quest_steps.iter().zip(&progress).all(|(step, count)| *count >= step.target_count);
+
+        let new_user_quest = NewUserQuest {
+            user_id,
+            quest_id: quest.id,
+            period_key,
+            progress,
+            updated_at: now,
+        };
+
+        let user_quest = upsert_user_quest(conn, &new_user_quest).await.map_err(|err| err.to_string())?;
+
+        if !is_completed || !claim_user_quest_completion(conn, user_quest.id, now).await.map_err(|err| err.to_string())? {
+            continue;
+        }
+
+        match do_mission(conn, cache_conn, &user_id.to_string(), &quest.mission_code, None, &[]).await {
+            Ok(()) => completed.push(quest),
+            Err(err) => {
+                eprintln!("Failed to reward quest: {} - {}", quest.code, err);
+
+                release_user_quest_completion(conn, user_quest.id).await.map_err(|err| err.to_string())?;
+            }
+        }
+    }
+
+    Ok(completed)
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use diesel::{ExpressionMethods, QueryDsl, dsl::count_star};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::{
+    config::db::DbConn,
+    models::{
+        category::CategoryGoogleType,
+        place::PlaceFilter,
+        recommendation::{RecommendationCandidate, ReturnRecommendedPlace},
+    },
+    schema::{places, reviews, user_place_access},
+    services::{
+        category::{get_categories, get_category_google_types, resolve_category},
+        place::get_unvisited_nearby_places,
+    },
+};
+
+/// Number of nearest unvisited places scored for a recommendation request. Farther places are
+/// never recommended.
+pub const CANDIDATE_LIMIT: i64 = 200;
+
+/// Affinity given to places of categories the user has no history with, so that new users and new
+/// categories still get recommendations driven by proximity and quality.
+const BASE_AFFINITY: f64 = 0.1;
+
+/// Weight of the user's review ratings in the category affinity, relative to their visit share.
+const REVIEW_WEIGHT: f64 = 0.5;
+
+/// Rating assumed for places without a Google rating.
+const DEFAULT_RATING: f64 = 3.0;
+
+/// Affinity of the user `user_id` for each category, from the share of their visits of that
+/// category, raised by good and lowered by bad ratings in their reviews of places of that category.
+/// Reviewed places are classified with `mappings`, as their visits were.
+pub async fn get_category_affinities(conn: &mut DbConn, user_id: Uuid, mappings: &[CategoryGoogleType]) -> Result<HashMap<String, f64>, diesel::result::Error> {
+    let visit_counts: Vec<(String, i64)> = user_place_access::table
+        .filter(user_place_access::user_id.eq(user_id))
+        .filter(user_place_access::type_.ne(""))
+        .group_by(user_place_access::type_)
+        .select((user_place_access::type_, count_star()))
+        .load(conn)
+        .await?;
+
+    let total_visits: i64 = visit_counts.iter().map(|(_, count)| count).sum();
+
+    let mut affinities: HashMap<String, f64> = visit_counts.into_iter().map(|(code, count)| (code, count as f64 / total_visits as f64)).collect();
+
+    let rated_places: Vec<(Option<Vec<Option<String>>>, f64)> = reviews::table
+        .inner_join(places::table)
+        .filter(reviews::user_id.eq(user_id))
+        .select((places::types, reviews::rating))
+        .load(conn)
+        .await?;
+
+    let mut rating_sums: HashMap<String, (f64, i32)> = HashMap::new();
+
+    for (types, rating) in rated_places {
+        let types: Vec<String> = types.unwrap_or_default().into_iter().flatten().collect();
+
+        if let Some(code) = resolve_category(mappings, &types) {
+            let entry = rating_sums.entry(code).or_default();
+
+            entry.0 += (rating - 3.0) / 2.0;
+            entry.1 += 1;
+        }
+    }
+
+    for (code, (sum, count)) in rating_sums {
+        // Review-only categories are ignored so that a single review does not drive recommendations.
+        if let Some(affinity) = affinities.get_mut(&code) {
+            *affinity = (*affinity + REVIEW_WEIGHT * sum / count as f64).max(0.0);
+        }
+    }
+
+    Ok(affinities)
+}
+
+/// Scores `candidate` for a user with category `affinities`, as category affinity × proximity ×
+/// quality.
+///
+/// # Returns
+/// The score and the reason of the recommendation.
+///
+/// # Behavior
+/// - Category affinity is the user's affinity for the place category, at least `BASE_AFFINITY`.
+/// - Proximity halves every `radius` meters.
+/// - Quality grows with the Google rating, its number of ratings and the trending score.
+///
+pub fn score_place(affinities: &HashMap<String, f64>, candidate: &RecommendationCandidate, radius: f64) -> (f64, String) {
+    let category_affinity = candidate.category.and_then(|category| affinities.get(&category.code).map(|affinity| (category, *affinity)));
+
+    let affinity = category_affinity.map_or(BASE_AFFINITY, |(_, affinity)| affinity.max(BASE_AFFINITY));
+
+    let proximity = 0.5_f64.powf(candidate.distance / radius);
+
+    let rating = candidate.rating.unwrap_or(DEFAULT_RATING) / 5.0;
+    let ratings_total = candidate.user_ratings_total.unwrap_or(0).max(0) as f64;
+    let quality = rating * (1.0 + ratings_total.ln_1p() / 10.0) * (1.0 + candidate.trending_score.ln_1p() / 10.0);
+
+    let reason = match category_affinity {
+        Some((category, affinity)) if affinity > BASE_AFFINITY => format!("Because you visit {} places", category.name),
+        _ if candidate.trending_score > 0.0 => "Trending near you".to_string(),
+        _ => "Popular near you".to_string(),
+    };
+
+    (affinity * proximity * quality, reason)
+}
+
+/// Places within `radius` meters of (`lat`, `lng`), matching `filter`, that the user
+/// `user_id` has not visited yet, best score first.
+///
+/// # Behavior
+/// Only the `CANDIDATE_LIMIT` nearest of these places are scored, so at most `CANDIDATE_LIMIT`
+/// places are returned.
+///
+pub async fn get_recommended_places(conn: &mut DbConn, user_id: Uuid, lat: f64, lng: f64, radius: f64, filter: PlaceFilter<'_>) -> Result<Vec<ReturnRecommendedPlace>, diesel::result::Error> {
+    let categories = get_categories(conn).await?;
+
+    let mappings = get_category_google_types(conn).await?;
+
+    let affinities = get_category_affinities(conn, user_id, &mappings).await?;
+
+    let candidates = get_unvisited_nearby_places(conn, user_id, lat, lng, radius, filter, CANDIDATE_LIMIT).await?;
+
+    let mut recommendations: Vec<ReturnRecommendedPlace> = candidates
+        .into_iter()
+        .map(|(place, distance)| {
+            let types: Vec<String> = place.types.iter().flatten().flatten().cloned().collect();
+
+            let category = resolve_category(&mappings, &types).and_then(|code| categories.iter().find(|category| category.code == code));
+
+            let candidate = RecommendationCandidate {
+                category,
+                rating: place.rating,
+                user_ratings_total: place.user_ratings_total,
+                trending_score: place.trending_score,
+                distance,
+            };
+
+            let (score, reason) = score_place(&affinities, &candidate, radius);
+
+            ReturnRecommendedPlace { place, distance, score, reason }
+        })
+        .collect();
+
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    Ok(recommendations)
+}
@@ -1,16 +1,19 @@
-use diesel::{
-    ExpressionMethods, SelectableHelper,
-    query_dsl::methods::{FilterDsl, SelectDsl},
-};
+use std::collections::HashSet;
+
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, dsl::count_star};
 use diesel_async::RunQueryDsl;
+use serde_json::Value;
 use uuid::Uuid;
 
 use crate::{
     config::db::DbConn,
-    models::review::{NewReview, Review},
+    models::review::{MAX_RATING, MIN_RATING, NewReview, Review, ReviewStats},
     schema::reviews,
 };
 
+/// Maximum number of photos returned in `ReviewStats::recent_photos`.
+const RECENT_PHOTO_LIMIT: usize = 10;
+
 pub async fn get_reviews(conn: &mut DbConn, place_id: &str) -> Result<Vec<Review>, diesel::result::Error> {
     let place_uuid = match Uuid::parse_str(place_id) {
         Ok(uuid) => uuid,
@@ -23,3 +26,79 @@ pub async fn get_reviews(conn: &mut DbConn, place_id: &str) -> Result<Vec<Review
 pub async fn create_review(conn: &mut DbConn, payload: &NewReview) -> Result<Review, diesel::result::Error> {
     diesel::insert_into(reviews::table).values(payload).returning(Review::as_returning()).get_result::<Review>(conn).await
 }
+
+/// Aggregates the reviews written by our users on the place `place_id`; imported Google reviews
+/// are excluded, as are ratings outside `MIN_RATING..=MAX_RATING` so the histogram and the average
+/// always cover the same reviews.
+pub async fn get_review_stats(conn: &mut DbConn, place_id: Uuid) -> Result<ReviewStats, diesel::result::Error> {
+    let rating_counts: Vec<(f64, i64)> = reviews::table
+        .filter(reviews::place_id.eq(place_id))
+        .filter(reviews::user_id.is_not_null())
+        .filter(reviews::rating.between(MIN_RATING, MAX_RATING))
+        .group_by(reviews::rating)
+        .select((reviews::rating, count_star()))
+        .load(conn)
+        .await?;
+
+    let mut stats = ReviewStats::default();
+    let mut rating_sum = 0.0;
+
+    for (rating, count) in rating_counts {
+        let star = rating.round() as usize;
+
+        stats.rating_histogram[star - 1] += count;
+        stats.review_count += count;
+        rating_sum += rating * count as f64;
+    }
+
+    if stats.review_count > 0 {
+        stats.average_rating = Some(rating_sum / stats.review_count as f64);
+    }
+
+    let medias: Vec<Option<Vec<Option<Value>>>> = reviews::table
+        .filter(reviews::place_id.eq(place_id))
+        .filter(reviews::user_id.is_not_null())
+        .filter(reviews::medias.is_not_null())
+        .order(reviews::created_at.desc())
+        .limit(RECENT_PHOTO_LIMIT as i64)
+        .select(reviews::medias)
+        .load(conn)
+        .await?;
+
+    stats.recent_photos = medias.into_iter().flatten().flatten().flatten().take(RECENT_PHOTO_LIMIT).collect();
+
+    Ok(stats)
+}
+
+/// Inserts the Google reviews of `reviews` not yet stored for the place `place_id`; a Google review
+/// is identified by its author and time.
+///
+/// # Returns
+/// The number of inserted reviews.
+///
+pub async fn create_missing_google_reviews(conn: &mut DbConn, place_id: Uuid, reviews: &[NewReview]) -> Result<usize, diesel::result::Error> {
+    let mut existing_keys: HashSet<(Option<String>, Option<i32>)> = reviews::table
+        .filter(reviews::place_id.eq(place_id))
+        .filter(reviews::user_id.is_null())
+        .select((reviews::author_name, reviews::time))
+        .load::<(Option<String>, Option<i32>)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let new_reviews: Vec<NewReview> = reviews
+        .iter()
+        .filter(|review| existing_keys.insert((review.author_name.clone(), review.time)))
+        .map(|review| NewReview {
+            user_id: None,
+            place_id,
+            ..review.clone()
+        })
+        .collect();
+
+    if new_reviews.is_empty() {
+        return Ok(0);
+    }
+
+    diesel::insert_into(reviews::table).values(&new_reviews).execute(conn).await
+}
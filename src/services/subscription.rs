@@ -1,13 +1,16 @@
-use diesel::{ExpressionMethods, SelectableHelper, query_dsl::methods::FilterDsl};
-use diesel_async::RunQueryDsl;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
 use uuid::Uuid;
 
 use crate::{
     config::db::DbConn,
-    models::subscription::{NewSubscription, Subscription},
+    models::subscription::{GIFT_SUBSCRIPTION_APP, NewSubscription, Subscription},
     schema::subscriptions,
 };
 
+/// The subscription of the user deciding their access to `app_type`: among the ones already
+/// started, the uncancelled one ending last, so cancelled rows never hide an active one.
 pub async fn get_subscription_by_user(conn: &mut DbConn, user_id: &str, app_type: &str) -> Result<Subscription, diesel::result::Error> {
     let user_uuid = match Uuid::parse_str(user_id) {
         Ok(uuid) => uuid,
@@ -16,7 +19,9 @@ pub async fn get_subscription_by_user(conn: &mut DbConn, user_id: &str, app_type
 
     subscriptions::table
         .filter(subscriptions::user_id.eq(user_uuid))
-        .filter(subscriptions::app.eq(app_type))
+        .filter(subscriptions::app.eq_any([app_type, GIFT_SUBSCRIPTION_APP]))
+        .filter(subscriptions::start_date.le(Utc::now().naive_utc()))
+        .order((subscriptions::is_cancelled.asc(), subscriptions::end_date.desc()))
         .first::<Subscription>(conn)
         .await
 }
@@ -28,3 +33,62 @@ pub async fn create_subscription<'a>(conn: &mut DbConn, payload: &'a NewSubscrip
         .get_result::<Subscription>(conn)
         .await
 }
+
+/// End of a gift of `days` granted at `now`, stacking on the current gift ending at `current_end`.
+pub fn get_gift_end_date(current_end: Option<NaiveDateTime>, now: NaiveDateTime, days: i32) -> NaiveDateTime {
+    current_end.filter(|end| *end > now).unwrap_or(now) + Duration::days(days as i64)
+}
+
+/// Grants `days` of premium access as a gift subscription.
+///
+/// # Behavior
+/// While a gift is running, its row is locked and its `end_date` extended, so stacked gifts stay
+/// a single subscription that has already started. Otherwise a new gift starts now.
+///
+pub async fn extend_gift_subscription(conn: &mut DbConn, user_id: Uuid, days: i32, source: &str) -> Result<Subscription, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let now = Utc::now().naive_utc();
+
+            let current = diesel::QueryDsl::for_update(
+                subscriptions::table
+                    .filter(subscriptions::user_id.eq(user_id))
+                    .filter(subscriptions::app.eq(GIFT_SUBSCRIPTION_APP))
+                    .filter(subscriptions::is_cancelled.eq(false))
+                    .filter(subscriptions::start_date.le(now))
+                    .filter(subscriptions::end_date.gt(now)),
+            )
+            .order(subscriptions::end_date.desc())
+            .select(Subscription::as_select())
+            .first::<Subscription>(conn)
+            .await
+            .optional()?;
+
+            if let Some(current) = current {
+                return diesel::update(subscriptions::table.filter(subscriptions::id.eq(current.id)))
+                    .set(subscriptions::end_date.eq(get_gift_end_date(Some(current.end_date), now, days)))
+                    .returning(Subscription::as_returning())
+                    .get_result::<Subscription>(conn)
+                    .await;
+            }
+
+            let payload = NewSubscription {
+                user_id,
+                environment: GIFT_SUBSCRIPTION_APP,
+                orig_tx_id: source,
+                latest_receipt: "",
+                start_date: now,
+                end_date: get_gift_end_date(None, now, days),
+                app: GIFT_SUBSCRIPTION_APP,
+                product_id: "premium",
+                is_cancelled: false,
+                validation_response: "",
+                fake: true,
+            };
+
+            create_subscription(conn, &payload).await
+        }
+        .scope_boxed()
+    })
+    .await
+}
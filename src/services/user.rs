@@ -1,30 +1,41 @@
+use chrono::{Duration, NaiveDateTime};
 use diesel::{
-    ExpressionMethods, SelectableHelper,
+    BoolExpressionMethods, ExpressionMethods, SelectableHelper,
     query_dsl::methods::{FilterDsl, SelectDsl},
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
 use uuid::Uuid;
 
 use crate::{
     config::db::DbConn,
-    models::user::{NewUser, User, UserPhotoChangeset},
+    models::{
+        achievement::AchievementEvent,
+        exp_history::NewExpHistory,
+        gift_reward::GiftReward,
+        level::Level,
+        user::{NewUser, PhotoField, User, UserPhotoChangeset, UserQuietHoursChangeset},
+    },
     schema::users,
+    services::{
+        achievement::evaluate_achievements,
+        exp_history::create_exp_history,
+        gift_reward::grant_gift_reward,
+        level::{get_levels, resolve_level_up},
+    },
+    utils::time::TIMEZONE_CHANGE_COOLDOWN_DAYS,
 };
 
-pub async fn update_user_photo(conn: &mut DbConn, id: &str, field: &str, url: &str) -> Result<User, diesel::result::Error> {
+pub async fn update_user_photo(conn: &mut DbConn, id: &str, field: PhotoField, url: &str) -> Result<User, diesel::result::Error> {
     let user_uuid = match Uuid::parse_str(id) {
         Ok(uuid) => uuid,
         Err(_) => return Err(diesel::result::Error::NotFound),
     };
 
-    let mut changes = UserPhotoChangeset { avatar_url: None, cover_url: None };
+    let mut changes = UserPhotoChangeset::default();
 
-    if field == "avatar_url" {
-        changes.avatar_url = Some(url.to_string());
-    }
-
-    if field == "cover_url" {
-        changes.cover_url = Some(url.to_string());
+    match field {
+        PhotoField::Avatar => changes.avatar_url = Some(url.to_string()),
+        PhotoField::Cover => changes.cover_url = Some(url.to_string()),
     }
 
     diesel::update(users::table.filter(users::id.eq(user_uuid)))
@@ -34,6 +45,30 @@ pub async fn update_user_photo(conn: &mut DbConn, id: &str, field: &str, url: &s
         .await
 }
 
+/// Changes the timezone of the user `id` unless it was changed within the last
+/// `TIMEZONE_CHANGE_COOLDOWN_DAYS`, in which case it fails with `NotFound`.
+pub async fn update_user_timezone(conn: &mut DbConn, id: Uuid, timezone: &str, now: NaiveDateTime) -> Result<User, diesel::result::Error> {
+    let cooldown_start = now - Duration::days(TIMEZONE_CHANGE_COOLDOWN_DAYS);
+
+    diesel::update(
+        users::table
+            .filter(users::id.eq(id))
+            .filter(users::timezone_updated_at.is_null().or(users::timezone_updated_at.le(cooldown_start))),
+    )
+    .set((users::timezone.eq(timezone), users::timezone_updated_at.eq(now)))
+    .returning(User::as_returning())
+    .get_result::<User>(conn)
+    .await
+}
+
+pub async fn update_user_quiet_hours(conn: &mut DbConn, id: Uuid, changes: &UserQuietHoursChangeset) -> Result<User, diesel::result::Error> {
+    diesel::update(users::table.filter(users::id.eq(id)))
+        .set(changes)
+        .returning(User::as_returning())
+        .get_result::<User>(conn)
+        .await
+}
+
 pub async fn get_user_by_id(conn: &mut DbConn, id: &str) -> Result<User, diesel::result::Error> {
     let user_uuid = match Uuid::parse_str(id) {
         Ok(uuid) => uuid,
@@ -51,42 +86,105 @@ pub async fn create_user(conn: &mut DbConn, payload: &NewUser) -> Result<User, d
     diesel::insert_into(users::table).values(payload).returning(User::as_returning()).get_result::<User>(conn).await
 }
 
-pub async fn give_exp_to_user(conn: &mut DbConn, id: &str, exp: i32) -> Result<(), diesel::result::Error> {
+/// Increments the user's EXP and appends the grant to the `exp_history` ledger in one transaction.
+/// `multiplier` is the EXP event multiplier already applied to `exp`, recorded in the ledger.
+pub async fn give_exp_to_user(conn: &mut DbConn, id: &str, exp: i32, source: &str, multiplier: f64) -> Result<(), diesel::result::Error> {
     let user_uuid = match Uuid::parse_str(id) {
         Ok(uuid) => uuid,
         Err(_) => return Err(diesel::result::Error::NotFound),
     };
 
-    diesel::update(users::table.filter(users::id.eq(user_uuid)))
-        .set(users::exp.eq(users::exp + exp))
-        .returning(User::as_returning())
-        .get_result::<User>(conn)
-        .await?;
+    let new_exp_history = NewExpHistory {
+        user_id: Some(user_uuid),
+        source: Some(source.to_string()),
+        amount: Some(exp),
+        multiplier,
+    };
 
-    Ok(())
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            diesel::update(users::table.filter(users::id.eq(user_uuid)))
+                .set(users::exp.eq(users::exp + exp))
+                .returning(User::as_returning())
+                .get_result::<User>(conn)
+                .await?;
+
+            create_exp_history(conn, &new_exp_history).await?;
+
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
 }
 
-pub async fn level_up(conn: &mut DbConn, id: &str) -> Result<bool, diesel::result::Error> {
+/// Applies the user's EXP to the level curve defined in `levels`.
+///
+/// # Returns
+/// The curve entries of every level reached, so their rewards can be granted. Empty if the user
+/// did not level up.
+///
+pub async fn level_up(conn: &mut DbConn, id: &str) -> Result<Vec<Level>, diesel::result::Error> {
     let user = get_user_by_id(conn, id).await?;
 
     let exp = user.exp.unwrap_or(0);
     let level = user.level.unwrap_or(0);
 
-    let level_up_exp = 200;
+    let levels = get_levels(conn).await?;
 
-    if exp >= level_up_exp {
-        let add_level = exp / level_up_exp;
-        let remain_exp = exp % level_up_exp;
+    let result = resolve_level_up(&levels, level, exp);
 
-        let user_uuid = Uuid::parse_str(id).map_err(|_| diesel::result::Error::NotFound)?;
+    if result.reached.is_empty() {
+        return Ok(vec![]);
+    }
 
-        diesel::update(users::table.filter(users::id.eq(user_uuid)))
-            .set((users::level.eq(level + add_level), users::exp.eq(remain_exp)))
-            .execute(conn)
-            .await?;
+    diesel::update(users::table.filter(users::id.eq(user.id)))
+        .set((users::level.eq(result.level), users::exp.eq(result.exp)))
+        .execute(conn)
+        .await?;
 
-        return Ok(true);
-    }
+    Ok(levels.into_iter().filter(|item| result.reached.contains(&item.level)).collect())
+}
+
+/// Locks the user's row until the end of the current transaction, serializing concurrent
+/// read-modify-writes of their EXP and level.
+pub async fn lock_user(conn: &mut DbConn, user_id: Uuid) -> Result<(), diesel::result::Error> {
+    diesel::QueryDsl::for_update(users::table.filter(users::id.eq(user_id)).select(users::id)).first::<Uuid>(conn).await?;
+
+    Ok(())
+}
+
+/// Gives EXP through `give_exp_to_user`, applies level ups, grants the gift rewards of every
+/// level reached and evaluates level achievements, in one transaction holding the user's row
+/// lock so concurrent grants cannot lose a level up.
+pub async fn give_exp_and_level_up(conn: &mut DbConn, id: &str, exp: i32, source: &str, multiplier: f64) -> Result<Vec<Level>, diesel::result::Error> {
+    let user_uuid = Uuid::parse_str(id).map_err(|_| diesel::result::Error::NotFound)?;
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            lock_user(conn, user_uuid).await?;
+
+            give_exp_to_user(conn, id, exp, source, multiplier).await?;
+
+            let reached_levels = level_up(conn, id).await?;
+
+            for level in &reached_levels {
+                if let Some(reward) = GiftReward::parse(level.reward_type.as_deref(), level.reward_count, None) {
+                    grant_gift_reward(conn, user_uuid, &reward, &format!("LEVEL_{}", level.level)).await?;
+                }
+            }
+
+            if !reached_levels.is_empty() {
+                evaluate_achievements(conn, user_uuid, AchievementEvent::LeveledUp).await?;
+            }
+
+            Ok(reached_levels)
+        }
+        .scope_boxed()
+    })
+    .await
+}
 
-    Ok(false)
+pub async fn get_users_by_ids(conn: &mut DbConn, ids: &[Uuid]) -> Result<Vec<User>, diesel::result::Error> {
+    users::table.filter(users::id.eq_any(ids)).select(User::as_select()).load(conn).await
 }
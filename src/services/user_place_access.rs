@@ -1,16 +1,133 @@
-use diesel::SelectableHelper;
+use chrono_tz::Tz;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+    dsl::{count_distinct, sql},
+    expression::SqlLiteral,
+    sql_types::{BigInt, Text, Uuid as SqlUuid},
+};
 use diesel_async::RunQueryDsl;
+use uuid::Uuid;
 
 use crate::{
     config::db::DbConn,
-    models::user_place_access::{NewUserPlaceAccess, UserPlaceAccess},
-    schema::user_place_access,
+    models::{
+        place::Place,
+        user_place_access::{MonthlyVisits, MostVisitedPlace, NewUserPlaceAccess, UserPlaceAccess, VisitStats, VisitTypeCount},
+    },
+    schema::{places, user_place_access},
 };
 
-pub async fn create_user_place_access(conn: &mut DbConn, payload: &NewUserPlaceAccess) -> Result<UserPlaceAccess, diesel::result::Error> {
+/// Number of places returned in `VisitStats::most_visited`.
+const MOST_VISITED_LIMIT: i64 = 5;
+
+/// Records the visit `payload`, unless the user already visited the place that day.
+///
+/// # Returns
+/// The recorded visit, or `None` when the place was already visited on `payload.visited_on`.
+///
+/// Number of visits, counting a place once per day.
+pub fn count_daily_visits() -> SqlLiteral<BigInt> {
+    sql::<BigInt>("count(distinct (user_place_access.place_id, user_place_access.visited_on))")
+}
+
+pub async fn create_user_place_access(conn: &mut DbConn, payload: &NewUserPlaceAccess) -> Result<Option<UserPlaceAccess>, diesel::result::Error> {
     diesel::insert_into(user_place_access::table)
         .values(payload)
+        .on_conflict((user_place_access::user_id, user_place_access::place_id, user_place_access::visited_on))
+        .do_nothing()
         .returning(UserPlaceAccess::as_returning())
         .get_result::<UserPlaceAccess>(conn)
         .await
+        .optional()
+}
+
+/// Visits of the user `user_id` with their place, newest first.
+///
+/// # Returns
+/// The page of visits and the total count of visits.
+///
+pub async fn get_visits_by_user(conn: &mut DbConn, user_id: Uuid, limit: i64, offset: i64) -> Result<(Vec<(UserPlaceAccess, Place)>, i64), diesel::result::Error> {
+    let total = user_place_access::table.filter(user_place_access::user_id.eq(user_id)).count().get_result::<i64>(conn).await?;
+
+    let visits = user_place_access::table
+        .inner_join(places::table)
+        .filter(user_place_access::user_id.eq(user_id))
+        .order(user_place_access::created_at.desc())
+        .limit(limit)
+        .offset(offset)
+        .select((UserPlaceAccess::as_select(), Place::as_select()))
+        .load(conn)
+        .await?;
+
+    Ok((visits, total))
+}
+
+/// Aggregated visits of the user `user_id`, with months computed in the user's timezone `tz`.
+/// A place counts once per day in every visit count.
+pub async fn get_visit_stats(conn: &mut DbConn, user_id: Uuid, tz: Tz) -> Result<VisitStats, diesel::result::Error> {
+    let (total_visits, distinct_places) = user_place_access::table
+        .filter(user_place_access::user_id.eq(user_id))
+        .select((count_daily_visits(), count_distinct(user_place_access::place_id)))
+        .first::<(i64, i64)>(conn)
+        .await?;
+
+    let by_type = user_place_access::table
+        .filter(user_place_access::user_id.eq(user_id))
+        .filter(user_place_access::type_.ne(""))
+        .group_by(user_place_access::type_)
+        .select((user_place_access::type_, count_daily_visits()))
+        .order(count_daily_visits().desc())
+        .load::<(String, i64)>(conn)
+        .await?
+        .into_iter()
+        .map(|(type_, visit_count)| VisitTypeCount { type_, visit_count })
+        .collect();
+
+    let visit_counts: Vec<(Uuid, i64)> = user_place_access::table
+        .filter(user_place_access::user_id.eq(user_id))
+        .group_by(user_place_access::place_id)
+        .select((user_place_access::place_id, count_distinct(user_place_access::visited_on)))
+        .order((count_distinct(user_place_access::visited_on).desc(), user_place_access::place_id.asc()))
+        .limit(MOST_VISITED_LIMIT)
+        .load(conn)
+        .await?;
+
+    let place_ids: Vec<Uuid> = visit_counts.iter().map(|(place_id, _)| *place_id).collect();
+
+    let mut places: Vec<Place> = places::table.filter(places::id.eq_any(&place_ids)).select(Place::as_select()).load(conn).await?;
+
+    let most_visited = visit_counts
+        .into_iter()
+        .filter_map(|(place_id, visit_count)| {
+            let index = places.iter().position(|place| place.id == place_id)?;
+
+            Some(MostVisitedPlace {
+                place: places.swap_remove(index),
+                visit_count,
+            })
+        })
+        .collect();
+
+    let monthly = diesel::sql_query(
+        "select to_char(created_at at time zone 'UTC' at time zone $2, 'YYYY-MM') as month, \
+           count(distinct (place_id, visited_on)) as visit_count, \
+           count(distinct place_id) as distinct_places \
+         from user_place_access \
+         where user_id = $1 \
+           and created_at >= (date_trunc('month', now() at time zone $2) - interval '11 months') at time zone $2 at time zone 'UTC' \
+         group by month \
+         order by month",
+    )
+    .bind::<SqlUuid, _>(user_id)
+    .bind::<Text, _>(tz.name())
+    .load::<MonthlyVisits>(conn)
+    .await?;
+
+    Ok(VisitStats {
+        total_visits,
+        distinct_places,
+        by_type,
+        most_visited,
+        monthly,
+    })
 }
@@ -0,0 +1,38 @@
+use imagesize::{ImageType, blob_size, image_type};
+
+const MIN_DIMENSION: usize = 128;
+const MAX_DIMENSION: usize = 4096;
+
+/// Validates that `data` is a JPEG, PNG or WebP image within the allowed dimensions.
+///
+/// The format is sniffed from the file header rather than trusted from the client's
+/// content type, and must agree with the declared `content_type`.
+///
+/// # Returns
+/// - `Ok(extension)` to store the file under, e.g. `"png"`.
+/// - `Err(String)` describing why the image was rejected.
+///
+pub fn validate_image(data: &[u8], content_type: &str) -> Result<&'static str, String> {
+    let (extension, expected_content_type) = match image_type(data).map_err(|_| "Unsupported image format.".to_string())? {
+        ImageType::Jpeg => ("jpg", "image/jpeg"),
+        ImageType::Png => ("png", "image/png"),
+        ImageType::Webp => ("webp", "image/webp"),
+        _ => return Err("Only JPEG, PNG and WebP images are allowed.".into()),
+    };
+
+    if content_type != expected_content_type {
+        return Err("Content type does not match the image data.".into());
+    }
+
+    let size = blob_size(data).map_err(|_| "Failed to read image dimensions.".to_string())?;
+
+    if size.width < MIN_DIMENSION || size.height < MIN_DIMENSION {
+        return Err(format!("Image must be at least {}x{} pixels.", MIN_DIMENSION, MIN_DIMENSION));
+    }
+
+    if size.width > MAX_DIMENSION || size.height > MAX_DIMENSION {
+        return Err(format!("Image must be at most {}x{} pixels.", MAX_DIMENSION, MAX_DIMENSION));
+    }
+
+    Ok(extension)
+}
@@ -13,6 +13,13 @@ pub struct Claims {
     pub exp: usize,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsubscribeClaims {
+    pub email: String,
+    pub category: String,
+    pub exp: usize,
+}
+
 pub fn sign_token(sub: String, email: String) -> Result<String, String> {
     let exp = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|err| err.to_string())?.as_secs() + 3600;
 
@@ -32,3 +39,23 @@ pub fn verify_token(token: &str) -> Result<TokenData<Claims>, String> {
 
     decode::<Claims>(&token, &DecodingKey::from_secret(secret_key.as_ref()), &Validation::default()).map_err(|err| err.to_string())
 }
+
+pub fn sign_unsubscribe_token(email: String, category: String) -> Result<String, String> {
+    let exp = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|err| err.to_string())?.as_secs() + 365 * 24 * 3600;
+
+    let claims = UnsubscribeClaims {
+        email,
+        category,
+        exp: exp.try_into().unwrap(),
+    };
+
+    let secret_key = env::var("JWT_SECRET_KEY").map_err(|err| err.to_string())?;
+
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret_key.as_ref())).map_err(|err| err.to_string())
+}
+
+pub fn verify_unsubscribe_token(token: &str) -> Result<TokenData<UnsubscribeClaims>, String> {
+    let secret_key = env::var("JWT_SECRET_KEY").map_err(|err| err.to_string())?;
+
+    decode::<UnsubscribeClaims>(token, &DecodingKey::from_secret(secret_key.as_ref()), &Validation::default()).map_err(|err| err.to_string())
+}
@@ -1,6 +1,6 @@
 use tera::{Context, Tera};
 
-pub fn invite_user_mail_body(invite_link: &str) -> Result<String, String> {
+pub fn invite_user_mail_body(invite_link: &str, unsubscribe_link: &str) -> Result<String, String> {
     let template = r#"
     <div style="font-family:Arial,sans-serif;background:#f4f6fb;padding:24px;">
         <table style="max-width:480px;margin:auto;background:#fff;border-radius:12px;box-shadow:0 2px 8px #eee;">
@@ -18,6 +18,9 @@ pub fn invite_user_mail_body(invite_link: &str) -> Result<String, String> {
               <p style="color:#999;font-size:13px;">
                 If you did not expect this invitation, please safely ignore this email.
               </p>
+              <p style="color:#999;font-size:12px;">
+                Don't want these emails? <a href="{{ unsubscribe_link }}" style="color:#999;">Unsubscribe</a>
+              </p>
             </td>
           </tr>
         </table>
@@ -29,6 +32,65 @@ pub fn invite_user_mail_body(invite_link: &str) -> Result<String, String> {
 
     let mut context = Context::new();
     context.insert("invite_link", invite_link);
+    context.insert("unsubscribe_link", unsubscribe_link);
 
     tera.render("invite.html", &context).map_err(|err| err.to_string())
 }
+
+pub fn unsubscribe_confirmation_page(email: &str, category: &str, token: &str) -> Result<String, String> {
+    let template = r#"
+    <div style="font-family:Arial,sans-serif;background:#f4f6fb;padding:24px;">
+        <table style="max-width:480px;margin:auto;background:#fff;border-radius:12px;box-shadow:0 2px 8px #eee;">
+          <tr>
+            <td style="padding:32px;">
+              <h2 style="color:#204080;">Unsubscribe</h2>
+              <p style="color:#333;font-size:16px;">
+                Stop sending {{ category }} emails to {{ email }}?
+              </p>
+              <form method="post" action="?token={{ token }}">
+                <button type="submit" style="margin:24px 0;padding:15px 32px;background:#3479f6;color:#fff;border:none;border-radius:6px;font-weight:bold;font-size:16px;cursor:pointer;">
+                  Unsubscribe
+                </button>
+              </form>
+            </td>
+          </tr>
+        </table>
+    </div>
+        "#;
+
+    let mut tera = Tera::default();
+    tera.add_raw_template("unsubscribe_confirmation.html", template).map_err(|err| err.to_string())?;
+
+    let mut context = Context::new();
+    context.insert("email", email);
+    context.insert("category", category);
+    context.insert("token", token);
+
+    tera.render("unsubscribe_confirmation.html", &context).map_err(|err| err.to_string())
+}
+
+pub fn unsubscribed_page(email: &str, category: &str) -> Result<String, String> {
+    let template = r#"
+    <div style="font-family:Arial,sans-serif;background:#f4f6fb;padding:24px;">
+        <table style="max-width:480px;margin:auto;background:#fff;border-radius:12px;box-shadow:0 2px 8px #eee;">
+          <tr>
+            <td style="padding:32px;">
+              <h2 style="color:#204080;">You're Unsubscribed</h2>
+              <p style="color:#333;font-size:16px;">
+                {{ email }} will no longer receive {{ category }} emails.
+              </p>
+            </td>
+          </tr>
+        </table>
+    </div>
+        "#;
+
+    let mut tera = Tera::default();
+    tera.add_raw_template("unsubscribed.html", template).map_err(|err| err.to_string())?;
+
+    let mut context = Context::new();
+    context.insert("email", email);
+    context.insert("category", category);
+
+    tera.render("unsubscribed.html", &context).map_err(|err| err.to_string())
+}
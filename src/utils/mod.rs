@@ -1,7 +1,9 @@
 pub mod apple;
 pub mod error_handling;
 pub mod hash;
+pub mod image;
 pub mod jwt;
 pub mod mail_template;
+pub mod pagination;
 pub mod time;
 pub mod tsp;
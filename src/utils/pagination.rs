@@ -0,0 +1,25 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Validate, Deserialize)]
+pub struct PaginationQuery {
+    #[validate(range(min = 1, max = 10000, message = "Page must be between 1 and 10000."))]
+    pub page: Option<i64>,
+
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100."))]
+    pub limit: Option<i64>,
+}
+
+impl PaginationQuery {
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1)
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(20)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1).saturating_mul(self.limit())
+    }
+}
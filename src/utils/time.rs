@@ -1,17 +1,124 @@
-use chrono::{Duration, Local, NaiveTime};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use validator::ValidationError;
 
-pub fn get_today() -> String {
-    Local::now().format("%d-%m-%y").to_string()
+/// Minimum days between two timezone changes of a user.
+pub const TIMEZONE_CHANGE_COOLDOWN_DAYS: i64 = 7;
+
+/// Timezone used when a user has none, or an unknown one, stored.
+pub const DEFAULT_TIMEZONE: Tz = Tz::UTC;
+
+/// Parses an IANA timezone name such as `Asia/Ho_Chi_Minh`.
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse::<Tz>().ok()
+}
+
+pub fn validate_timezone(name: &str) -> Result<(), ValidationError> {
+    if parse_timezone(name).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("timezone").with_message("Timezone must be an IANA timezone name, e.g. Asia/Ho_Chi_Minh.".into()))
+    }
+}
+
+/// The user's timezone, falling back to `DEFAULT_TIMEZONE` for unknown names.
+pub fn get_user_timezone(name: &str) -> Tz {
+    parse_timezone(name).unwrap_or(DEFAULT_TIMEZONE)
+}
+
+fn get_now(tz: Tz) -> DateTime<Tz> {
+    Utc::now().with_timezone(&tz)
+}
+
+/// Today in `tz` as an ISO date key, e.g. `2025-08-15`.
+pub fn get_today(tz: Tz) -> String {
+    get_now(tz).format("%Y-%m-%d").to_string()
+}
+
+pub fn get_today_date(tz: Tz) -> NaiveDate {
+    get_now(tz).date_naive()
+}
+
+/// Current ISO week in `tz`, e.g. `2025-W33`.
+pub fn get_this_week(tz: Tz) -> String {
+    let week = get_now(tz).iso_week();
+
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+/// Current month in `tz`, e.g. `2025-08`.
+pub fn get_this_month(tz: Tz) -> String {
+    get_now(tz).format("%Y-%m").to_string()
+}
+
+fn get_start_of(tz: Tz, date: NaiveDate) -> DateTime<Tz> {
+    // Midnight may not exist on DST transition days; the earliest valid instant of the day is
+    // used instead.
+    (0..24)
+        .find_map(|hour| date.and_time(NaiveTime::from_hms_opt(hour, 0, 0).unwrap()).and_local_timezone(tz).earliest())
+        .unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc().with_timezone(&tz))
+}
+
+fn get_seconds_to(tz: Tz, date: NaiveDate) -> i64 {
+    (get_start_of(tz, date) - get_now(tz)).num_seconds()
 }
 
-pub fn get_seconds_to_midnight() -> i64 {
-    let now = Local::now();
-    let tomorrow_midnight = (now + Duration::days(1))
-        .date_naive()
-        .and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap())
-        .and_local_timezone(Local)
-        .unwrap();
-    let duration = tomorrow_midnight - now;
+pub fn get_seconds_to_midnight(tz: Tz) -> i64 {
+    get_seconds_to(tz, get_today_date(tz) + Duration::days(1))
+}
+
+pub fn get_seconds_to_next_week(tz: Tz) -> i64 {
+    let today = get_today_date(tz);
+
+    get_seconds_to(tz, today + Duration::days(7 - today.weekday().num_days_from_monday() as i64))
+}
+
+pub fn get_seconds_to_next_month(tz: Tz) -> i64 {
+    let today = get_today_date(tz);
+    let next_month = if today.month() == 12 {
+        NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(today.year(), today.month() + 1, 1)
+    };
+
+    get_seconds_to(tz, next_month.unwrap())
+}
+
+/// Start of today in `tz` as a UTC timestamp, comparable with `created_at` columns.
+pub fn get_start_of_today(tz: Tz) -> NaiveDateTime {
+    get_start_of(tz, get_today_date(tz)).naive_utc()
+}
+
+/// Start of the current ISO week in `tz` as a UTC timestamp, comparable with `created_at` columns.
+pub fn get_start_of_week(tz: Tz) -> NaiveDateTime {
+    let today = get_today_date(tz);
+
+    get_start_of(tz, today - Duration::days(today.weekday().num_days_from_monday() as i64)).naive_utc()
+}
+
+/// Start of the current month in `tz` as a UTC timestamp, comparable with `created_at` columns.
+pub fn get_start_of_month(tz: Tz) -> NaiveDateTime {
+    get_start_of(tz, get_today_date(tz).with_day(1).unwrap()).naive_utc()
+}
+
+/// Whether a user whose timezone last changed at `updated_at` may change it again at `now`.
+pub fn is_timezone_change_allowed(updated_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    updated_at.is_none_or(|updated_at| now - updated_at >= Duration::days(TIMEZONE_CHANGE_COOLDOWN_DAYS))
+}
+
+/// Whether switching from `from` to `to` at `now` moves the local date back to one already
+/// used, which would reopen daily quotas and limits.
+pub fn is_date_moved_back(from: Tz, to: Tz, now: DateTime<Utc>) -> bool {
+    now.with_timezone(&to).date_naive() < now.with_timezone(&from).date_naive()
+}
+
+/// Current wall-clock time in `tz`.
+pub fn get_time_now(tz: Tz) -> NaiveTime {
+    get_now(tz).time()
+}
 
-    duration.num_seconds()
+/// Returns whether `now` falls inside the quiet hours window, which may wrap past midnight
+/// (e.g. 22:00 - 07:00).
+pub fn is_within_quiet_hours(start: NaiveTime, end: NaiveTime, now: NaiveTime) -> bool {
+    if start <= end { now >= start && now < end } else { now >= start || now < end }
 }